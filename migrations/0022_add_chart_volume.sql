@@ -0,0 +1,154 @@
+-- 차트 테이블에 거래량 컬럼 추가
+ALTER TABLE chart_1m
+    ADD COLUMN IF NOT EXISTS nad_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS token_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS trade_count INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE chart_5m
+    ADD COLUMN IF NOT EXISTS nad_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS token_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS trade_count INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE chart_15m
+    ADD COLUMN IF NOT EXISTS nad_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS token_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS trade_count INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE chart_30m
+    ADD COLUMN IF NOT EXISTS nad_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS token_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS trade_count INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE chart_1h
+    ADD COLUMN IF NOT EXISTS nad_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS token_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS trade_count INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE chart_4h
+    ADD COLUMN IF NOT EXISTS nad_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS token_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS trade_count INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE chart_1d
+    ADD COLUMN IF NOT EXISTS nad_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS token_volume NUMERIC NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS trade_count INTEGER NOT NULL DEFAULT 0;
+
+-- 차트 타입별 캔들 간격 (초)
+CREATE OR REPLACE FUNCTION chart_interval(chart_type TEXT)
+RETURNS BIGINT AS $$
+    SELECT CASE chart_type
+        WHEN '1m' THEN 60
+        WHEN '5m' THEN 300
+        WHEN '15m' THEN 900
+        WHEN '30m' THEN 1800
+        WHEN '1h' THEN 3600
+        WHEN '4h' THEN 14400
+        WHEN '1d' THEN 86400
+    END::BIGINT;
+$$ LANGUAGE sql IMMUTABLE;
+
+-- 캔들이 저장될 때 swap 테이블에서 해당 구간의 거래량을 계산
+CREATE OR REPLACE FUNCTION fill_chart_volume()
+RETURNS trigger AS $$
+DECLARE
+    bucket BIGINT;
+BEGIN
+    bucket := chart_interval(substring(TG_TABLE_NAME from 7));
+
+    SELECT
+        COALESCE(SUM(s.nad_amount), 0),
+        COALESCE(SUM(s.token_amount), 0),
+        COUNT(*)
+    INTO NEW.nad_volume, NEW.token_volume, NEW.trade_count
+    FROM swap s
+    WHERE s.token_id = NEW.token_id
+        AND s.created_at >= NEW.time_stamp
+        AND s.created_at < NEW.time_stamp + bucket;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- swap 이 캔들보다 늦게 들어온 경우 해당 캔들의 거래량을 다시 계산
+CREATE OR REPLACE FUNCTION refresh_chart_volume()
+RETURNS trigger AS $$
+BEGIN
+    UPDATE chart_1m SET trade_count = trade_count
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('1m');
+    UPDATE chart_5m SET trade_count = trade_count
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('5m');
+    UPDATE chart_15m SET trade_count = trade_count
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('15m');
+    UPDATE chart_30m SET trade_count = trade_count
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('30m');
+    UPDATE chart_1h SET trade_count = trade_count
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('1h');
+    UPDATE chart_4h SET trade_count = trade_count
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('4h');
+    UPDATE chart_1d SET trade_count = trade_count
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('1d');
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- 트리거 설정
+CREATE TRIGGER chart_1m_volume_trigger
+BEFORE INSERT OR UPDATE ON chart_1m
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+CREATE TRIGGER chart_5m_volume_trigger
+BEFORE INSERT OR UPDATE ON chart_5m
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+CREATE TRIGGER chart_15m_volume_trigger
+BEFORE INSERT OR UPDATE ON chart_15m
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+CREATE TRIGGER chart_30m_volume_trigger
+BEFORE INSERT OR UPDATE ON chart_30m
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+CREATE TRIGGER chart_1h_volume_trigger
+BEFORE INSERT OR UPDATE ON chart_1h
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+CREATE TRIGGER chart_4h_volume_trigger
+BEFORE INSERT OR UPDATE ON chart_4h
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+CREATE TRIGGER chart_1d_volume_trigger
+BEFORE INSERT OR UPDATE ON chart_1d
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+CREATE TRIGGER refresh_chart_volume_trigger
+AFTER INSERT ON swap
+FOR EACH ROW EXECUTE FUNCTION refresh_chart_volume();
+
+-- 복제 여부와 관계없이 항상 실행
+
+ALTER TABLE chart_1m ENABLE ALWAYS TRIGGER chart_1m_volume_trigger;
+ALTER TABLE chart_5m ENABLE ALWAYS TRIGGER chart_5m_volume_trigger;
+ALTER TABLE chart_15m ENABLE ALWAYS TRIGGER chart_15m_volume_trigger;
+ALTER TABLE chart_30m ENABLE ALWAYS TRIGGER chart_30m_volume_trigger;
+ALTER TABLE chart_1h ENABLE ALWAYS TRIGGER chart_1h_volume_trigger;
+ALTER TABLE chart_4h ENABLE ALWAYS TRIGGER chart_4h_volume_trigger;
+ALTER TABLE chart_1d ENABLE ALWAYS TRIGGER chart_1d_volume_trigger;
+ALTER TABLE swap ENABLE ALWAYS TRIGGER refresh_chart_volume_trigger;
+
+-- 기존 캔들 거래량 채우기
+UPDATE chart_1m SET trade_count = trade_count;
+UPDATE chart_5m SET trade_count = trade_count;
+UPDATE chart_15m SET trade_count = trade_count;
+UPDATE chart_30m SET trade_count = trade_count;
+UPDATE chart_1h SET trade_count = trade_count;
+UPDATE chart_4h SET trade_count = trade_count;
+UPDATE chart_1d SET trade_count = trade_count;
@@ -0,0 +1,20 @@
+CREATE OR REPLACE FUNCTION notify_new_king()
+RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify('new_king', json_build_object(
+        'token_id', NEW.token_id,
+        'record', row_to_json(NEW)
+    )::text);
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+
+-- 트리거 설정
+CREATE TRIGGER replicated_new_king_trigger
+AFTER INSERT ON king
+FOR EACH ROW EXECUTE FUNCTION notify_new_king();
+
+-- 복제 트리거 활성화
+ALTER TABLE king ENABLE REPLICA TRIGGER replicated_new_king_trigger;
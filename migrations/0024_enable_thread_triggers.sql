@@ -0,0 +1,3 @@
+-- API 서버에서 작성한 스레드도 이벤트를 발생시키도록 복제 여부와 관계없이 실행
+ALTER TABLE thread ENABLE ALWAYS TRIGGER replicated_thread_changes_trigger;
+ALTER TABLE token_reply_count ENABLE ALWAYS TRIGGER replicated_new_token_reply_trigger;
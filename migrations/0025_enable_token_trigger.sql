@@ -0,0 +1,2 @@
+-- 생성자가 API 서버에서 수정한 토큰 정보도 구독자에게 전달되도록 항상 실행
+ALTER TABLE token ENABLE ALWAYS TRIGGER replicated_new_token_trigger;
@@ -0,0 +1,20 @@
+-- 토큰 검색용 전문 검색 / 트라이그램 인덱스
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- 이름과 심볼은 설명보다 높은 가중치
+CREATE OR REPLACE FUNCTION token_search_vector(name VARCHAR, symbol VARCHAR, description TEXT)
+RETURNS tsvector AS $$
+    SELECT setweight(to_tsvector('simple'::regconfig, COALESCE(name, '')), 'A')
+        || setweight(to_tsvector('simple'::regconfig, COALESCE(symbol, '')), 'A')
+        || setweight(to_tsvector('simple'::regconfig, COALESCE(description, '')), 'C');
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE INDEX IF NOT EXISTS token_search_vector_index
+    ON token USING GIN (token_search_vector(name, symbol, description));
+
+-- 오타 허용 검색과 접두사 검색 (LIKE 'abc%')
+CREATE INDEX IF NOT EXISTS token_name_trgm_index ON token USING GIN (LOWER(name) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS token_symbol_trgm_index ON token USING GIN (LOWER(symbol) gin_trgm_ops);
+
+-- 컨트랙트 주소 접두사 검색
+CREATE INDEX IF NOT EXISTS token_id_pattern_index ON token (LOWER(id) text_pattern_ops);
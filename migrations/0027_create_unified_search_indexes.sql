@@ -0,0 +1,7 @@
+-- 통합 검색: 닉네임 오타 허용 / 접두사 검색
+CREATE INDEX IF NOT EXISTS account_nickname_trgm_index ON account USING GIN (LOWER(nickname) gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS account_id_lower_index ON account (LOWER(id));
+
+-- 붙여넣은 트랜잭션 해시 조회 (대소문자 무시)
+CREATE INDEX IF NOT EXISTS swap_transaction_hash_lower_index ON swap (LOWER(transaction_hash));
+CREATE INDEX IF NOT EXISTS token_create_transaction_hash_lower_index ON token (LOWER(create_transaction_hash));
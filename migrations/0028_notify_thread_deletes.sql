@@ -0,0 +1,31 @@
+-- 스레드 삭제도 thread_change 이벤트를 발생시키도록 DELETE 추가
+CREATE OR REPLACE FUNCTION notify_thread_changes()
+RETURNS trigger AS $$
+DECLARE
+    record thread;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        record := OLD;
+    ELSE
+        record := NEW;
+    END IF;
+
+    PERFORM pg_notify('thread_change', json_build_object(
+        'operation', TG_OP,
+        'token_id', record.token_id,
+        'record', row_to_json(record)
+    )::text);
+
+    RETURN record;
+END;
+$$ LANGUAGE plpgsql;
+
+
+-- 트리거 재설정
+DROP TRIGGER IF EXISTS replicated_thread_changes_trigger ON thread;
+CREATE TRIGGER replicated_thread_changes_trigger
+AFTER INSERT OR UPDATE OR DELETE ON thread
+FOR EACH ROW EXECUTE FUNCTION notify_thread_changes();
+
+-- API 서버에서 삭제한 스레드도 이벤트를 발생시키도록 항상 실행
+ALTER TABLE thread ENABLE ALWAYS TRIGGER replicated_thread_changes_trigger;
@@ -0,0 +1,10 @@
+-- king 행이 갱신될 때도 new_king 이벤트를 발생시키도록 UPDATE 추가
+DROP TRIGGER IF EXISTS replicated_new_king_trigger ON king;
+
+-- 트리거 설정
+CREATE TRIGGER replicated_new_king_trigger
+AFTER INSERT OR UPDATE ON king
+FOR EACH ROW EXECUTE FUNCTION notify_new_king();
+
+-- 복제 트리거 활성화
+ALTER TABLE king ENABLE REPLICA TRIGGER replicated_new_king_trigger;
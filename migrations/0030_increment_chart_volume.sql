@@ -0,0 +1,242 @@
+-- 캔들 구간별 거래량 계산을 위한 인덱스
+CREATE INDEX IF NOT EXISTS swap_token_id_created_at_index ON swap (token_id, created_at);
+
+-- 새 swap 의 거래량을 해당 구간 캔들에 더함
+CREATE OR REPLACE FUNCTION refresh_chart_volume()
+RETURNS trigger AS $$
+BEGIN
+    UPDATE chart_1m
+    SET nad_volume = nad_volume + NEW.nad_amount,
+        token_volume = token_volume + NEW.token_amount,
+        trade_count = trade_count + 1
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('1m');
+    UPDATE chart_5m
+    SET nad_volume = nad_volume + NEW.nad_amount,
+        token_volume = token_volume + NEW.token_amount,
+        trade_count = trade_count + 1
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('5m');
+    UPDATE chart_15m
+    SET nad_volume = nad_volume + NEW.nad_amount,
+        token_volume = token_volume + NEW.token_amount,
+        trade_count = trade_count + 1
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('15m');
+    UPDATE chart_30m
+    SET nad_volume = nad_volume + NEW.nad_amount,
+        token_volume = token_volume + NEW.token_amount,
+        trade_count = trade_count + 1
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('30m');
+    UPDATE chart_1h
+    SET nad_volume = nad_volume + NEW.nad_amount,
+        token_volume = token_volume + NEW.token_amount,
+        trade_count = trade_count + 1
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('1h');
+    UPDATE chart_4h
+    SET nad_volume = nad_volume + NEW.nad_amount,
+        token_volume = token_volume + NEW.token_amount,
+        trade_count = trade_count + 1
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('4h');
+    UPDATE chart_1d
+    SET nad_volume = nad_volume + NEW.nad_amount,
+        token_volume = token_volume + NEW.token_amount,
+        trade_count = trade_count + 1
+    WHERE token_id = NEW.token_id
+        AND time_stamp = NEW.created_at - NEW.created_at % chart_interval('1d');
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- 캔들 생성 시에만 구간의 거래량을 계산 (이후 swap 은 refresh_chart_volume 이 더함)
+DROP TRIGGER IF EXISTS chart_1m_volume_trigger ON chart_1m;
+CREATE TRIGGER chart_1m_volume_trigger
+BEFORE INSERT ON chart_1m
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+DROP TRIGGER IF EXISTS chart_5m_volume_trigger ON chart_5m;
+CREATE TRIGGER chart_5m_volume_trigger
+BEFORE INSERT ON chart_5m
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+DROP TRIGGER IF EXISTS chart_15m_volume_trigger ON chart_15m;
+CREATE TRIGGER chart_15m_volume_trigger
+BEFORE INSERT ON chart_15m
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+DROP TRIGGER IF EXISTS chart_30m_volume_trigger ON chart_30m;
+CREATE TRIGGER chart_30m_volume_trigger
+BEFORE INSERT ON chart_30m
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+DROP TRIGGER IF EXISTS chart_1h_volume_trigger ON chart_1h;
+CREATE TRIGGER chart_1h_volume_trigger
+BEFORE INSERT ON chart_1h
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+DROP TRIGGER IF EXISTS chart_4h_volume_trigger ON chart_4h;
+CREATE TRIGGER chart_4h_volume_trigger
+BEFORE INSERT ON chart_4h
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+DROP TRIGGER IF EXISTS chart_1d_volume_trigger ON chart_1d;
+CREATE TRIGGER chart_1d_volume_trigger
+BEFORE INSERT ON chart_1d
+FOR EACH ROW EXECUTE FUNCTION fill_chart_volume();
+
+-- 복제 여부와 관계없이 항상 실행
+ALTER TABLE chart_1m ENABLE ALWAYS TRIGGER chart_1m_volume_trigger;
+ALTER TABLE chart_5m ENABLE ALWAYS TRIGGER chart_5m_volume_trigger;
+ALTER TABLE chart_15m ENABLE ALWAYS TRIGGER chart_15m_volume_trigger;
+ALTER TABLE chart_30m ENABLE ALWAYS TRIGGER chart_30m_volume_trigger;
+ALTER TABLE chart_1h ENABLE ALWAYS TRIGGER chart_1h_volume_trigger;
+ALTER TABLE chart_4h ENABLE ALWAYS TRIGGER chart_4h_volume_trigger;
+ALTER TABLE chart_1d ENABLE ALWAYS TRIGGER chart_1d_volume_trigger;
+
+-- 거래량만 바뀐 경우에는 new_chart 이벤트를 발생시키지 않도록 가격 컬럼 갱신에만 반응
+DROP TRIGGER IF EXISTS chart_1m_changes_trigger ON chart_1m;
+CREATE TRIGGER chart_1m_changes_trigger
+AFTER INSERT OR UPDATE OF open_price, close_price, high_price, low_price ON chart_1m
+FOR EACH ROW EXECUTE FUNCTION notify_new_chart();
+
+DROP TRIGGER IF EXISTS chart_5m_changes_trigger ON chart_5m;
+CREATE TRIGGER chart_5m_changes_trigger
+AFTER INSERT OR UPDATE OF open_price, close_price, high_price, low_price ON chart_5m
+FOR EACH ROW EXECUTE FUNCTION notify_new_chart();
+
+DROP TRIGGER IF EXISTS chart_15m_changes_trigger ON chart_15m;
+CREATE TRIGGER chart_15m_changes_trigger
+AFTER INSERT OR UPDATE OF open_price, close_price, high_price, low_price ON chart_15m
+FOR EACH ROW EXECUTE FUNCTION notify_new_chart();
+
+DROP TRIGGER IF EXISTS chart_30m_changes_trigger ON chart_30m;
+CREATE TRIGGER chart_30m_changes_trigger
+AFTER INSERT OR UPDATE OF open_price, close_price, high_price, low_price ON chart_30m
+FOR EACH ROW EXECUTE FUNCTION notify_new_chart();
+
+DROP TRIGGER IF EXISTS chart_1h_changes_trigger ON chart_1h;
+CREATE TRIGGER chart_1h_changes_trigger
+AFTER INSERT OR UPDATE OF open_price, close_price, high_price, low_price ON chart_1h
+FOR EACH ROW EXECUTE FUNCTION notify_new_chart();
+
+DROP TRIGGER IF EXISTS chart_4h_changes_trigger ON chart_4h;
+CREATE TRIGGER chart_4h_changes_trigger
+AFTER INSERT OR UPDATE OF open_price, close_price, high_price, low_price ON chart_4h
+FOR EACH ROW EXECUTE FUNCTION notify_new_chart();
+
+DROP TRIGGER IF EXISTS chart_1d_changes_trigger ON chart_1d;
+CREATE TRIGGER chart_1d_changes_trigger
+AFTER INSERT OR UPDATE OF open_price, close_price, high_price, low_price ON chart_1d
+FOR EACH ROW EXECUTE FUNCTION notify_new_chart();
+
+-- 복제 트리거로 설정
+ALTER TABLE chart_1m ENABLE REPLICA TRIGGER chart_1m_changes_trigger;
+ALTER TABLE chart_5m ENABLE REPLICA TRIGGER chart_5m_changes_trigger;
+ALTER TABLE chart_15m ENABLE REPLICA TRIGGER chart_15m_changes_trigger;
+ALTER TABLE chart_30m ENABLE REPLICA TRIGGER chart_30m_changes_trigger;
+ALTER TABLE chart_1h ENABLE REPLICA TRIGGER chart_1h_changes_trigger;
+ALTER TABLE chart_4h ENABLE REPLICA TRIGGER chart_4h_changes_trigger;
+ALTER TABLE chart_1d ENABLE REPLICA TRIGGER chart_1d_changes_trigger;
+
+-- 기존 캔들 거래량 다시 채우기
+UPDATE chart_1m c
+SET nad_volume = v.nad_volume, token_volume = v.token_volume, trade_count = v.trade_count
+FROM (
+    SELECT
+        token_id,
+        created_at - created_at % chart_interval('1m') AS time_stamp,
+        SUM(nad_amount) AS nad_volume,
+        SUM(token_amount) AS token_volume,
+        COUNT(*) AS trade_count
+    FROM swap
+    GROUP BY 1, 2
+) v
+WHERE c.token_id = v.token_id AND c.time_stamp = v.time_stamp;
+
+UPDATE chart_5m c
+SET nad_volume = v.nad_volume, token_volume = v.token_volume, trade_count = v.trade_count
+FROM (
+    SELECT
+        token_id,
+        created_at - created_at % chart_interval('5m') AS time_stamp,
+        SUM(nad_amount) AS nad_volume,
+        SUM(token_amount) AS token_volume,
+        COUNT(*) AS trade_count
+    FROM swap
+    GROUP BY 1, 2
+) v
+WHERE c.token_id = v.token_id AND c.time_stamp = v.time_stamp;
+
+UPDATE chart_15m c
+SET nad_volume = v.nad_volume, token_volume = v.token_volume, trade_count = v.trade_count
+FROM (
+    SELECT
+        token_id,
+        created_at - created_at % chart_interval('15m') AS time_stamp,
+        SUM(nad_amount) AS nad_volume,
+        SUM(token_amount) AS token_volume,
+        COUNT(*) AS trade_count
+    FROM swap
+    GROUP BY 1, 2
+) v
+WHERE c.token_id = v.token_id AND c.time_stamp = v.time_stamp;
+
+UPDATE chart_30m c
+SET nad_volume = v.nad_volume, token_volume = v.token_volume, trade_count = v.trade_count
+FROM (
+    SELECT
+        token_id,
+        created_at - created_at % chart_interval('30m') AS time_stamp,
+        SUM(nad_amount) AS nad_volume,
+        SUM(token_amount) AS token_volume,
+        COUNT(*) AS trade_count
+    FROM swap
+    GROUP BY 1, 2
+) v
+WHERE c.token_id = v.token_id AND c.time_stamp = v.time_stamp;
+
+UPDATE chart_1h c
+SET nad_volume = v.nad_volume, token_volume = v.token_volume, trade_count = v.trade_count
+FROM (
+    SELECT
+        token_id,
+        created_at - created_at % chart_interval('1h') AS time_stamp,
+        SUM(nad_amount) AS nad_volume,
+        SUM(token_amount) AS token_volume,
+        COUNT(*) AS trade_count
+    FROM swap
+    GROUP BY 1, 2
+) v
+WHERE c.token_id = v.token_id AND c.time_stamp = v.time_stamp;
+
+UPDATE chart_4h c
+SET nad_volume = v.nad_volume, token_volume = v.token_volume, trade_count = v.trade_count
+FROM (
+    SELECT
+        token_id,
+        created_at - created_at % chart_interval('4h') AS time_stamp,
+        SUM(nad_amount) AS nad_volume,
+        SUM(token_amount) AS token_volume,
+        COUNT(*) AS trade_count
+    FROM swap
+    GROUP BY 1, 2
+) v
+WHERE c.token_id = v.token_id AND c.time_stamp = v.time_stamp;
+
+UPDATE chart_1d c
+SET nad_volume = v.nad_volume, token_volume = v.token_volume, trade_count = v.trade_count
+FROM (
+    SELECT
+        token_id,
+        created_at - created_at % chart_interval('1d') AS time_stamp,
+        SUM(nad_amount) AS nad_volume,
+        SUM(token_amount) AS token_volume,
+        COUNT(*) AS trade_count
+    FROM swap
+    GROUP BY 1, 2
+) v
+WHERE c.token_id = v.token_id AND c.time_stamp = v.time_stamp;
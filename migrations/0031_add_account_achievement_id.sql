@@ -0,0 +1,2 @@
+-- 업적 보유자 목록의 keyset 페이지네이션을 위한 id 추가
+ALTER TABLE account_achievement ADD COLUMN IF NOT EXISTS id SERIAL UNIQUE;
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use api_server::{
+    db::postgres::{controller::achievement::AchievementController, PostgresDatabase},
+    types::achievement::ACHIEVEMENT_RULES,
+};
+use tracing::info;
+
+/// Evaluates every achievement rule against the full history and awards
+/// whatever the live evaluator has missed.
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv::dotenv().ok();
+    tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::INFO)
+        .init();
+    let postgres = Arc::new(PostgresDatabase::new().await);
+    let achievement_controller = AchievementController::new(postgres);
+
+    for definition in ACHIEVEMENT_RULES.iter() {
+        let awarded = achievement_controller.evaluate(definition, None).await?;
+        info!("Backfilled {}: {} new award(s)", definition.name, awarded);
+    }
+
+    Ok(())
+}
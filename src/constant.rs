@@ -6,7 +6,8 @@ pub mod change_channels {
     pub const BALANCE: &str = "balance_change";
     pub const TOKEN_REPLIES_COUNT: &str = "new_token_reply";
     pub const THREAD: &str = "thread_change";
-    pub const ALL: [&str; 7] = [
+    pub const KING: &str = "new_king";
+    pub const ALL: [&str; 8] = [
         TOKEN,
         CURVE,
         SWAP,
@@ -14,5 +15,6 @@ pub mod change_channels {
         BALANCE,
         THREAD,
         TOKEN_REPLIES_COUNT,
+        KING,
     ];
 }
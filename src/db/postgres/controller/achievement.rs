@@ -0,0 +1,315 @@
+use anyhow::Result;
+use sqlx::FromRow;
+
+use std::sync::Arc;
+
+use crate::{
+    db::postgres::PostgresDatabase,
+    types::{
+        achievement::{AchievementDefinition, AchievementRule},
+        model::{Account, Achievement},
+        pagination::{Cursor, Pagination},
+    },
+};
+
+pub struct AchievementController {
+    pub db: Arc<PostgresDatabase>,
+}
+
+#[derive(FromRow)]
+struct HolderRow {
+    #[sqlx(flatten)]
+    account: Account,
+    award_id: i32,
+}
+
+impl AchievementController {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        AchievementController { db }
+    }
+
+    pub async fn get_achievement(&self, name: &str) -> Result<Option<Achievement>> {
+        let achievement = sqlx::query_as!(
+            Achievement,
+            "SELECT * FROM achievement WHERE name = $1",
+            name
+        )
+        .fetch_optional(&self.db.pool)
+        .await?;
+
+        Ok(achievement)
+    }
+
+    /// Returns a page of the accounts that earned the achievement, most followed first
+    pub async fn get_holders(
+        &self,
+        achievement_id: i32,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Account>, Option<String>)> {
+        let query = r#"
+            SELECT
+                a.id, a.nickname, a.bio, a.image_uri, a.follower_count, a.following_count, a.like_count,
+                aa.id as award_id
+            FROM account_achievement aa
+            JOIN account a ON aa.account_id = a.id
+            WHERE aa.achievement_id = $1
+                AND ($2::BIGINT IS NULL OR (a.follower_count::BIGINT, aa.id) < ($2, $3))
+            ORDER BY a.follower_count DESC, aa.id DESC
+            LIMIT $4
+            "#;
+
+        let rows = sqlx::query_as::<_, HolderRow>(query)
+            .bind(achievement_id)
+            .bind(pagination.cursor.map(|cursor| cursor.key))
+            .bind(pagination.cursor.map(|cursor| cursor.id))
+            .bind(pagination.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let next_cursor = pagination.next_cursor(
+            rows.len(),
+            rows.last().map(|row| Cursor {
+                key: row.account.follower_count.into(),
+                id: row.award_id,
+            }),
+        );
+        let holders = rows.into_iter().map(|row| row.account).collect();
+        Ok((holders, next_cursor))
+    }
+
+    /// Awards the achievement to every account satisfying its rule, optionally
+    /// scoped to a single token. Already earned achievements are left untouched,
+    /// so this is safe to run repeatedly. Returns the number of new awards.
+    pub async fn evaluate(
+        &self,
+        definition: &AchievementDefinition,
+        token_id: Option<&str>,
+    ) -> Result<u64> {
+        let result = match definition.rule {
+            AchievementRule::ListedCreator => {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO account_achievement (account_id, achievement_id)
+                    SELECT DISTINCT t.creator, ac.id
+                    FROM token t
+                    JOIN achievement ac ON ac.name = $1
+                    WHERE t.is_listing
+                    AND ($2::VARCHAR IS NULL OR t.id = $2)
+                    ON CONFLICT DO NOTHING
+                    "#,
+                    definition.name,
+                    token_id
+                )
+                .execute(&self.db.pool)
+                .await?
+            }
+            AchievementRule::TopBuyer { drawdown } => {
+                sqlx::query!(
+                    r#"
+                    WITH top_buy AS (
+                        SELECT DISTINCT ON (s.token_id)
+                            s.token_id,
+                            s.sender,
+                            s.nad_amount / s.token_amount AS price
+                        FROM swap s
+                        WHERE s.is_buy
+                        AND s.token_amount > 0
+                        AND ($2::VARCHAR IS NULL OR s.token_id = $2)
+                        ORDER BY s.token_id, s.nad_amount / s.token_amount DESC, s.id
+                    )
+                    INSERT INTO account_achievement (account_id, achievement_id)
+                    SELECT DISTINCT tb.sender, ac.id
+                    FROM top_buy tb
+                    JOIN curve c ON c.token_id = tb.token_id
+                    JOIN achievement ac ON ac.name = $1
+                    WHERE c.price * 100 <= tb.price * (100 - $3)
+                    ON CONFLICT DO NOTHING
+                    "#,
+                    definition.name,
+                    token_id,
+                    drawdown
+                )
+                .execute(&self.db.pool)
+                .await?
+            }
+        };
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::types::{achievement::ACHIEVEMENT_RULES, pagination::MAX_PAGE_LIMIT};
+
+    fn definition(name: &str) -> &'static AchievementDefinition {
+        ACHIEVEMENT_RULES
+            .iter()
+            .find(|definition| definition.name == name)
+            .unwrap()
+    }
+
+    async fn seed(pool: &PgPool) {
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO account (id, nickname, image_uri) VALUES
+                ('0xcreator', 'creator', ''),
+                ('0xalice', 'alice', ''),
+                ('0xbob', 'bob', '');
+            INSERT INTO token (id, name, symbol, image_uri, creator, is_listing, created_at, create_transaction_hash) VALUES
+                ('0xlisted', 'Listed', 'LST', '', '0xcreator', TRUE, 0, '0xt1'),
+                ('0xcurving', 'Curving', 'CRV', '', '0xalice', FALSE, 0, '0xt2');
+            -- alice paid 2 NAD per token, the top buy; bob paid 1
+            INSERT INTO swap (sender, token_id, is_buy, nad_amount, token_amount, created_at, transaction_hash) VALUES
+                ('0xalice', '0xcurving', TRUE, 200, 100, 1, '0xs1'),
+                ('0xbob', '0xcurving', TRUE, 100, 100, 2, '0xs2'),
+                ('0xbob', '0xcurving', FALSE, 50, 100, 3, '0xs3');
+            INSERT INTO curve (id, token_id, virtual_nad, virtual_token, reserve_token, latest_trade_at, price, created_at) VALUES
+                ('0xcurve', '0xcurving', 0, 0, 0, 0, 1.01, 0);
+            "#,
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn holders(controller: &AchievementController, name: &str) -> Vec<String> {
+        let achievement = controller.get_achievement(name).await.unwrap().unwrap();
+        controller
+            .get_holders(
+                achievement.id,
+                &Pagination {
+                    cursor: None,
+                    limit: MAX_PAGE_LIMIT,
+                },
+            )
+            .await
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|account| account.id)
+            .collect()
+    }
+
+    async fn set_price(pool: &PgPool, price: &str) {
+        sqlx::query("UPDATE curve SET price = $1::NUMERIC WHERE token_id = '0xcurving'")
+            .bind(price)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[sqlx::test]
+    async fn listed_creator_is_awarded_once(pool: PgPool) {
+        seed(&pool).await;
+        let controller = AchievementController::new(Arc::new(PostgresDatabase { pool }));
+        let chad_creator = definition("chad_creator");
+
+        assert_eq!(controller.evaluate(chad_creator, None).await.unwrap(), 1);
+        assert_eq!(controller.evaluate(chad_creator, None).await.unwrap(), 0);
+        assert_eq!(
+            holders(&controller, "chad_creator").await,
+            vec!["0xcreator"]
+        );
+    }
+
+    #[sqlx::test]
+    async fn evaluation_is_scoped_to_token(pool: PgPool) {
+        seed(&pool).await;
+        let controller = AchievementController::new(Arc::new(PostgresDatabase { pool }));
+        let chad_creator = definition("chad_creator");
+
+        assert_eq!(
+            controller
+                .evaluate(chad_creator, Some("0xcurving"))
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            controller
+                .evaluate(chad_creator, Some("0xlisted"))
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[sqlx::test]
+    async fn left_curve_waits_for_drawdown(pool: PgPool) {
+        seed(&pool).await;
+        let controller =
+            AchievementController::new(Arc::new(PostgresDatabase { pool: pool.clone() }));
+        let left_curve = definition("left_curve");
+
+        // 49.5% below the top buy of 2
+        assert_eq!(controller.evaluate(left_curve, None).await.unwrap(), 0);
+
+        // Exactly 50% below
+        set_price(&pool, "1.0").await;
+        assert_eq!(controller.evaluate(left_curve, None).await.unwrap(), 1);
+        assert_eq!(controller.evaluate(left_curve, None).await.unwrap(), 0);
+        assert_eq!(holders(&controller, "left_curve").await, vec!["0xalice"]);
+
+        // Never taken back, even once the price recovers
+        set_price(&pool, "3.0").await;
+        assert_eq!(controller.evaluate(left_curve, None).await.unwrap(), 0);
+        assert_eq!(holders(&controller, "left_curve").await, vec!["0xalice"]);
+    }
+
+    #[sqlx::test]
+    async fn holders_are_paged_by_followers(pool: PgPool) {
+        seed(&pool).await;
+        sqlx::raw_sql(
+            r#"
+            UPDATE account SET follower_count = 5 WHERE id = '0xcreator';
+            UPDATE account SET follower_count = 3 WHERE id IN ('0xalice', '0xbob');
+            INSERT INTO account_achievement (account_id, achievement_id)
+            SELECT account_id, ac.id
+            FROM (VALUES ('0xcreator'), ('0xalice'), ('0xbob')) AS v(account_id), achievement ac
+            WHERE ac.name = 'chad_creator';
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let controller =
+            AchievementController::new(Arc::new(PostgresDatabase { pool: pool.clone() }));
+        let achievement = controller
+            .get_achievement("chad_creator")
+            .await
+            .unwrap()
+            .unwrap();
+        let ids = |holders: Vec<Account>| -> Vec<String> {
+            holders.into_iter().map(|account| account.id).collect()
+        };
+
+        let mut pagination = Pagination {
+            cursor: None,
+            limit: 2,
+        };
+        let (first, next_cursor) = controller
+            .get_holders(achievement.id, &pagination)
+            .await
+            .unwrap();
+        // Ties on followers go to the latest award first
+        assert_eq!(ids(first), vec!["0xcreator", "0xbob"]);
+        assert_eq!(next_cursor.as_deref(), Some("3_3"));
+
+        // Follower counts moving between pages neither repeat nor skip holders
+        sqlx::query("UPDATE account SET follower_count = 9 WHERE id = '0xbob'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pagination.cursor = next_cursor.map(|cursor| cursor.parse().unwrap());
+        let (second, next_cursor) = controller
+            .get_holders(achievement.id, &pagination)
+            .await
+            .unwrap();
+        assert_eq!(ids(second), vec!["0xalice"]);
+        assert_eq!(next_cursor, None);
+    }
+}
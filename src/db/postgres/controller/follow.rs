@@ -0,0 +1,85 @@
+use anyhow::Result;
+
+use std::sync::Arc;
+
+use crate::db::postgres::PostgresDatabase;
+
+pub struct FollowController {
+    pub db: Arc<PostgresDatabase>,
+}
+
+impl FollowController {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        FollowController { db }
+    }
+
+    /// Returns `false` if `follower_id` already follows `following_id`
+    pub async fn follow(&self, follower_id: &str, following_id: &str) -> Result<bool> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO follow (follower_id, following_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            follower_id,
+            following_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "UPDATE account SET following_count = following_count + 1 WHERE id = $1",
+            follower_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE account SET follower_count = follower_count + 1 WHERE id = $1",
+            following_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Returns `false` if `follower_id` doesn't follow `following_id`
+    pub async fn unfollow(&self, follower_id: &str, following_id: &str) -> Result<bool> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let deleted = sqlx::query!(
+            "DELETE FROM follow WHERE follower_id = $1 AND following_id = $2",
+            follower_id,
+            following_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if deleted == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "UPDATE account SET following_count = following_count - 1 WHERE id = $1",
+            follower_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE account SET follower_count = follower_count - 1 WHERE id = $1",
+            following_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+}
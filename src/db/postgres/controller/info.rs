@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 
 use std::sync::Arc;
 
@@ -61,6 +61,6 @@ impl InfoController {
         )
         .fetch_one(&self.db.pool)
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to fetch token and user info: {}", e))
+        .context("Failed to fetch token and user info")
     }
 }
@@ -0,0 +1,63 @@
+use anyhow::{Context, Result};
+
+use std::sync::Arc;
+
+use crate::{
+    db::postgres::{controller::order::OrderController, PostgresDatabase},
+    types::{event::king::KingToken, model::King},
+};
+
+pub struct KingController {
+    pub db: Arc<PostgresDatabase>,
+}
+
+impl KingController {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        KingController { db }
+    }
+
+    /// Returns the latest `limit` kings, newest first.
+    pub async fn get_kings(&self, limit: i64) -> Result<Vec<KingToken>> {
+        let kings = sqlx::query_as!(
+            King,
+            "SELECT * FROM king ORDER BY created_at DESC LIMIT $1",
+            limit
+        )
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        let ids: Vec<String> = kings.iter().map(|king| king.token_id.clone()).collect();
+        let order_controller = OrderController::new(self.db.clone());
+        let tokens = order_controller.get_order_token_responses(&ids).await?;
+
+        let kings = kings
+            .into_iter()
+            .filter_map(|king| {
+                let token = tokens.iter().find(|token| token.id == king.token_id)?;
+                Some(KingToken {
+                    token: token.clone(),
+                    crowned_at: king.created_at,
+                })
+            })
+            .collect();
+
+        Ok(kings)
+    }
+
+    pub async fn get_current_king(&self) -> Result<Option<KingToken>> {
+        Ok(self.get_kings(1).await?.into_iter().next())
+    }
+
+    pub async fn get_king_token(&self, king: King) -> Result<KingToken> {
+        let order_controller = OrderController::new(self.db.clone());
+        let token = order_controller
+            .get_order_token_response_by_token(&king.token_id)
+            .await
+            .context("Failed to fetch king token")?;
+
+        Ok(KingToken {
+            token,
+            crowned_at: king.created_at,
+        })
+    }
+}
@@ -0,0 +1,342 @@
+use anyhow::Result;
+use sqlx::FromRow;
+
+use std::sync::Arc;
+
+use crate::{
+    db::postgres::PostgresDatabase,
+    types::{
+        event::UserInfo,
+        like::{LikedAccountsResponse, LikedThreadsResponse, LikersResponse},
+        model::{Account, Thread},
+        pagination::{Cursor, Pagination},
+        thread::ThreadPost,
+    },
+};
+
+/// `thread_likes.created_at` in microseconds, used as the keyset for thread likes
+const THREAD_LIKE_KEY: &str = "(EXTRACT(EPOCH FROM tl.created_at) * 1000000)::BIGINT";
+
+pub struct LikeController {
+    pub db: Arc<PostgresDatabase>,
+}
+
+#[derive(FromRow)]
+struct LikerRow {
+    #[sqlx(flatten)]
+    account: Account,
+    like_id: i32,
+    sort_key: i64,
+}
+
+#[derive(FromRow)]
+struct AccountLikeRow {
+    #[sqlx(flatten)]
+    account: Account,
+    like_id: i32,
+}
+
+#[derive(FromRow)]
+struct LikedThreadRow {
+    #[sqlx(flatten)]
+    thread: Thread,
+    author_nickname: String,
+    author_image_uri: String,
+    like_id: i32,
+    sort_key: i64,
+}
+
+impl LikeController {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        LikeController { db }
+    }
+
+    pub async fn get_likes_received(
+        &self,
+        account_id: &str,
+        pagination: &Pagination<i32>,
+    ) -> Result<LikersResponse> {
+        let query = r#"
+            SELECT
+                a.id, a.nickname, a.bio, a.image_uri, a.follower_count, a.following_count, a.like_count,
+                al.id as like_id
+            FROM account_like al
+            JOIN account a ON al.liker_id = a.id
+            WHERE al.liking_id = $1
+                AND ($2::INT IS NULL OR al.id < $2)
+            ORDER BY al.id DESC
+            LIMIT $3
+            "#;
+
+        let rows = sqlx::query_as::<_, AccountLikeRow>(query)
+            .bind(account_id)
+            .bind(pagination.cursor)
+            .bind(pagination.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let (likers, next_cursor) = Self::into_accounts(rows, pagination);
+        Ok(LikersResponse {
+            likers,
+            next_cursor,
+        })
+    }
+
+    pub async fn get_thread_likers(
+        &self,
+        thread_id: i32,
+        pagination: &Pagination,
+    ) -> Result<LikersResponse> {
+        let query = format!(
+            r#"
+            SELECT
+                a.id, a.nickname, a.bio, a.image_uri, a.follower_count, a.following_count, a.like_count,
+                tl.id as like_id,
+                {key} as sort_key
+            FROM thread_likes tl
+            JOIN account a ON tl.user_id = a.id
+            WHERE tl.thread_id = $1
+                AND ($2::BIGINT IS NULL OR ({key}, tl.id) < ($2, $3))
+            ORDER BY sort_key DESC, tl.id DESC
+            LIMIT $4
+            "#,
+            key = THREAD_LIKE_KEY
+        );
+
+        let rows = sqlx::query_as::<_, LikerRow>(&query)
+            .bind(thread_id)
+            .bind(pagination.cursor.map(|cursor| cursor.key))
+            .bind(pagination.cursor.map(|cursor| cursor.id))
+            .bind(pagination.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        Ok(Self::into_likers(rows, pagination))
+    }
+
+    pub async fn get_liked_accounts(
+        &self,
+        account_id: &str,
+        pagination: &Pagination<i32>,
+    ) -> Result<LikedAccountsResponse> {
+        let query = r#"
+            SELECT
+                a.id, a.nickname, a.bio, a.image_uri, a.follower_count, a.following_count, a.like_count,
+                al.id as like_id
+            FROM account_like al
+            JOIN account a ON al.liking_id = a.id
+            WHERE al.liker_id = $1
+                AND ($2::INT IS NULL OR al.id < $2)
+            ORDER BY al.id DESC
+            LIMIT $3
+            "#;
+
+        let rows = sqlx::query_as::<_, AccountLikeRow>(query)
+            .bind(account_id)
+            .bind(pagination.cursor)
+            .bind(pagination.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let (accounts, next_cursor) = Self::into_accounts(rows, pagination);
+        Ok(LikedAccountsResponse {
+            accounts,
+            next_cursor,
+        })
+    }
+
+    pub async fn get_liked_threads(
+        &self,
+        account_id: &str,
+        pagination: &Pagination,
+    ) -> Result<LikedThreadsResponse> {
+        let query = format!(
+            r#"
+            SELECT
+                t.*,
+                a.nickname as author_nickname,
+                a.image_uri as author_image_uri,
+                tl.id as like_id,
+                {key} as sort_key
+            FROM thread_likes tl
+            JOIN thread t ON tl.thread_id = t.id
+            JOIN account a ON t.author_id = a.id
+            WHERE tl.user_id = $1
+                AND ($2::BIGINT IS NULL OR ({key}, tl.id) < ($2, $3))
+            ORDER BY sort_key DESC, tl.id DESC
+            LIMIT $4
+            "#,
+            key = THREAD_LIKE_KEY
+        );
+
+        let rows = sqlx::query_as::<_, LikedThreadRow>(&query)
+            .bind(account_id)
+            .bind(pagination.cursor.map(|cursor| cursor.key))
+            .bind(pagination.cursor.map(|cursor| cursor.id))
+            .bind(pagination.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let next_cursor = pagination.next_cursor(
+            rows.len(),
+            rows.last().map(|row| Cursor {
+                key: row.sort_key,
+                id: row.like_id,
+            }),
+        );
+        let threads = rows
+            .into_iter()
+            .map(|row| ThreadPost {
+                thread: row.thread,
+                author: UserInfo {
+                    nickname: row.author_nickname,
+                    image_uri: row.author_image_uri,
+                },
+            })
+            .collect();
+
+        Ok(LikedThreadsResponse {
+            threads,
+            next_cursor,
+        })
+    }
+
+    /// Returns `false` if `liker_id` already likes `liking_id`
+    pub async fn like_account(&self, liker_id: &str, liking_id: &str) -> Result<bool> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO account_like (liker_id, liking_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            liker_id,
+            liking_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "UPDATE account SET like_count = like_count + 1 WHERE id = $1",
+            liking_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Returns `false` if `liker_id` doesn't like `liking_id`
+    pub async fn unlike_account(&self, liker_id: &str, liking_id: &str) -> Result<bool> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let deleted = sqlx::query!(
+            "DELETE FROM account_like WHERE liker_id = $1 AND liking_id = $2",
+            liker_id,
+            liking_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if deleted == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "UPDATE account SET like_count = like_count - 1 WHERE id = $1",
+            liking_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Returns `false` if `user_id` already likes the thread
+    pub async fn like_thread(&self, thread_id: i32, user_id: &str) -> Result<bool> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO thread_likes (thread_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            thread_id,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "UPDATE thread SET likes_count = likes_count + 1 WHERE id = $1",
+            thread_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Returns `false` if `user_id` doesn't like the thread
+    pub async fn unlike_thread(&self, thread_id: i32, user_id: &str) -> Result<bool> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let deleted = sqlx::query!(
+            "DELETE FROM thread_likes WHERE thread_id = $1 AND user_id = $2",
+            thread_id,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if deleted == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            "UPDATE thread SET likes_count = likes_count - 1 WHERE id = $1",
+            thread_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    fn into_likers(rows: Vec<LikerRow>, pagination: &Pagination) -> LikersResponse {
+        let next_cursor = pagination.next_cursor(
+            rows.len(),
+            rows.last().map(|row| Cursor {
+                key: row.sort_key,
+                id: row.like_id,
+            }),
+        );
+        LikersResponse {
+            likers: rows.into_iter().map(|row| row.account).collect(),
+            next_cursor,
+        }
+    }
+
+    fn into_accounts(
+        rows: Vec<AccountLikeRow>,
+        pagination: &Pagination<i32>,
+    ) -> (Vec<Account>, Option<String>) {
+        let next_cursor = pagination.next_cursor(rows.len(), rows.last().map(|row| row.like_id));
+        let accounts = rows.into_iter().map(|row| row.account).collect();
+        (accounts, next_cursor)
+    }
+}
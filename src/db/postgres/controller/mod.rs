@@ -1,6 +1,15 @@
 pub mod info;
 pub mod tokenpage;
 
+pub mod achievement;
+pub mod follow;
+pub mod king;
+pub mod like;
 pub mod new_content;
 pub mod order;
 pub mod profile;
+pub mod search;
+pub mod session;
+pub mod thread;
+pub mod token;
+pub mod trade;
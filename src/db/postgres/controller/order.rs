@@ -1,13 +1,17 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 use std::sync::Arc;
 use tracing::info;
 
 use crate::{
     db::postgres::PostgresDatabase,
-    types::event::{
-        order::{OrderTokenResponse, OrderType},
-        UserInfo,
+    types::{
+        event::{
+            order::{market_cap_usd, OrderTokenResponse, OrderType},
+            UserInfo,
+        },
+        model::Curve,
     },
 };
 
@@ -16,6 +20,15 @@ pub struct OrderController {
 }
 
 const ORDER_LIMIT: i64 = 50;
+
+/// SQL for a token's market cap in wei, see `Curve::market_cap`
+pub(crate) fn market_cap_sql(curve: &str) -> String {
+    format!(
+        "ROUND({curve}.price * GREATEST({supply} - {curve}.reserve_token, 0))",
+        curve = curve,
+        supply = Curve::total_supply()
+    )
+}
 #[derive(Debug, FromRow)]
 pub struct TokenWithScore {
     #[sqlx(flatten)]
@@ -31,6 +44,7 @@ struct OrderTokenResponseRaw {
     pub description: Option<String>,
     pub reply_count: Option<String>,
     pub price: Option<String>,
+    pub market_cap: Option<String>,
     pub creator: serde_json::Value,
     pub created_at: Option<i64>,
 }
@@ -47,7 +61,7 @@ impl OrderController {
         &self,
         token_id: &str,
     ) -> Result<OrderTokenResponse> {
-        let token_response: OrderTokenResponseRaw = sqlx::query_as(
+        let token_response: OrderTokenResponseRaw = sqlx::query_as(&format!(
             r#"
             SELECT
                 t.id,
@@ -58,6 +72,7 @@ impl OrderController {
                 t.created_at,
                 COALESCE(crc.reply_count::TEXT, '0') as reply_count,
                 COALESCE(cu.price::TEXT, '0') as price,
+                COALESCE({market_cap}::TEXT, '0') as market_cap,
                 json_build_object(
                     'nickname', a.nickname,
                     'image_uri', a.image_uri
@@ -68,7 +83,8 @@ impl OrderController {
             LEFT JOIN curve cu ON t.id = cu.token_id
             WHERE t.id = $1
             "#,
-        )
+            market_cap = market_cap_sql("cu")
+        ))
         .bind(token_id)
         .fetch_one(&self.db.pool)
         .await
@@ -85,11 +101,17 @@ impl OrderController {
             description: token_response.description.unwrap_or_default(),
             reply_count: token_response.reply_count.unwrap_or_default(),
             price: token_response.price.unwrap_or_default(),
+            market_cap_usd: token_response
+                .market_cap
+                .as_deref()
+                .and_then(market_cap_usd),
+            market_cap: token_response.market_cap.unwrap_or_default(),
             user_info,
             created_at: token_response.created_at.unwrap_or_default(),
         })
     }
     async fn get_ordered_tokens(&self, order_type: OrderType) -> Result<Vec<TokenWithScore>> {
+        let market_cap_query;
         let id_scores_query = match order_type {
             OrderType::CreationTime => {
                 r#"
@@ -102,12 +124,16 @@ impl OrderController {
             }
 
             OrderType::MarketCap => {
-                r#"
-                SELECT token_id as id,price::TEXT as score 
-                FROM curve
-                ORDER BY price DESC
-                LIMIT $1
-                "#
+                market_cap_query = format!(
+                    r#"
+                    SELECT token_id as id, {market_cap}::TEXT as score
+                    FROM curve cu
+                    ORDER BY {market_cap} DESC
+                    LIMIT $1
+                    "#,
+                    market_cap = market_cap_sql("cu")
+                );
+                &market_cap_query
             }
 
             OrderType::ReplyCount => {
@@ -119,10 +145,12 @@ impl OrderController {
                 "#
             }
             OrderType::LatestReply => {
+                // Newest thread per token in epoch milliseconds, see `latest_reply_score`
                 r#"
-                SELECT DISTINCT ON (token_id) token_id as id, EXTRACT(EPOCH FROM created_at)::TEXT as score 
+                SELECT token_id as id, (EXTRACT(EPOCH FROM MAX(created_at)) * 1000)::BIGINT::TEXT as score
                 FROM thread
-                ORDER BY token_id, created_at DESC 
+                GROUP BY token_id
+                ORDER BY MAX(created_at) DESC
                 LIMIT $1
                 "#
             }
@@ -135,13 +163,6 @@ impl OrderController {
                 "#
             }
         };
-        let token_responses_query = r#"
-        SELECT t.id, t.name, t.symbol, t.image_uri, t.description,t.created_at, COALESCE(crc.reply_count::TEXT, '0') as reply_count, COALESCE(cu.price::TEXT, '0') as price, json_build_object('nickname', a.nickname, 'image_uri', a.image_uri) as creator
-        FROM token t
-        LEFT JOIN account a ON t.creator = a.id 
-        LEFT JOIN token_reply_count crc ON t.id = crc.token_id
-        LEFT JOIN curve cu ON t.id = cu.token_id 
-        WHERE t.id = ANY($1)"#;
         let id_scores: Vec<IdScore> = sqlx::query_as(id_scores_query)
             .bind(ORDER_LIMIT)
             .fetch_all(&self.db.pool)
@@ -150,32 +171,18 @@ impl OrderController {
         info!("id_scores: {:?}", id_scores);
         let ids: Vec<String> = id_scores.iter().map(|row| row.id.clone()).collect();
 
-        let token_responses: Vec<OrderTokenResponseRaw> = sqlx::query_as(token_responses_query)
-            .bind(&ids[..])
-            .fetch_all(&self.db.pool)
-            .await
-            .context("Failed to fetch detailed token information")?;
+        let token_responses = self.get_order_token_responses(&ids).await?;
 
         let tokens_with_score = id_scores
             .into_iter()
             .filter_map(|id_score| {
-                let token_raw = token_responses
+                let token = token_responses
                     .iter()
-                    .find(|c| c.id.as_ref().map_or(false, |id| id == &id_score.id))?;
-                let user_info: UserInfo = serde_json::from_value(token_raw.creator.clone()).ok()?;
+                    .find(|token| token.id == id_score.id)?
+                    .clone();
 
                 Some(TokenWithScore {
-                    token: OrderTokenResponse {
-                        id: token_raw.id.clone().unwrap_or_default(),
-                        name: token_raw.name.clone().unwrap_or_default(),
-                        symbol: token_raw.symbol.clone().unwrap_or_default(),
-                        image_uri: token_raw.image_uri.clone().unwrap_or_default(),
-                        description: token_raw.description.clone().unwrap_or_default(),
-                        reply_count: token_raw.reply_count.clone().unwrap_or_default(),
-                        price: token_raw.price.clone().unwrap_or_default(),
-                        user_info,
-                        created_at: token_raw.created_at.clone().unwrap_or_default(),
-                    },
+                    token,
                     score: id_score.score.unwrap_or_default(),
                 })
             })
@@ -184,58 +191,26 @@ impl OrderController {
         Ok(tokens_with_score)
     }
 
-    pub async fn get_creation_time_order_token(&self) -> Result<Vec<TokenWithScore>> {
-        self.get_ordered_tokens(OrderType::CreationTime).await
-    }
-
-    pub async fn get_market_cap_order_token(&self) -> Result<Vec<TokenWithScore>> {
-        self.get_ordered_tokens(OrderType::MarketCap).await
-    }
-
-    pub async fn get_reply_count_order_token(&self) -> Result<Vec<TokenWithScore>> {
-        self.get_ordered_tokens(OrderType::ReplyCount).await
-    }
-
-    pub async fn get_latest_reply_order_token(&self) -> Result<Vec<TokenWithScore>> {
-        self.get_ordered_tokens(OrderType::LatestReply).await
-    }
-
-    pub async fn get_bump_order_token(&self) -> Result<Vec<TokenWithScore>> {
-        self.get_ordered_tokens(OrderType::Bump).await
-    }
-
-    // TODO: 어떤 식으로 정렬할지
-    pub async fn search_order_tokens(&self, query: &str) -> Result<Vec<OrderTokenResponse>> {
-        let token_responses: Vec<OrderTokenResponseRaw> = sqlx::query_as(
+    pub async fn get_order_token_responses(
+        &self,
+        token_ids: &[String],
+    ) -> Result<Vec<OrderTokenResponse>> {
+        let token_responses: Vec<OrderTokenResponseRaw> = sqlx::query_as(&format!(
             r#"
-            SELECT 
-                t.id,
-                t.name,
-                t.symbol,
-                t.image_uri,
-                t.description,
-                t.created_at,
-                COALESCE(crc.reply_count::TEXT, '0') as reply_count,
-                COALESCE(cu.price::TEXT, '0') as price,
-                json_build_object(
-                    'nickname', a.nickname,
-                    'image_uri', a.image_uri
-                ) as creator
-            FROM token t
-            LEFT JOIN account a ON t.creator = a.id
-            LEFT JOIN token_reply_count crc ON t.id = crc.token
-            LEFT JOIN curve cu ON t.id = cu.token
-            WHERE LOWER(t.name) LIKE $1 OR LOWER(t.symbol) LIKE $1
-            ORDER BY cu.price DESC NULLS LAST
-            LIMIT 50
-            "#,
-        )
-        .bind(query)
+        SELECT t.id, t.name, t.symbol, t.image_uri, t.description,t.created_at, COALESCE(crc.reply_count::TEXT, '0') as reply_count, COALESCE(cu.price::TEXT, '0') as price, COALESCE({market_cap}::TEXT, '0') as market_cap, json_build_object('nickname', a.nickname, 'image_uri', a.image_uri) as creator
+        FROM token t
+        LEFT JOIN account a ON t.creator = a.id 
+        LEFT JOIN token_reply_count crc ON t.id = crc.token_id
+        LEFT JOIN curve cu ON t.id = cu.token_id 
+        WHERE t.id = ANY($1)"#,
+            market_cap = market_cap_sql("cu")
+        ))
+        .bind(token_ids)
         .fetch_all(&self.db.pool)
         .await
-        .context("Failed to fetch token information")?;
-        info!("token_responses: {:?}", token_responses);
-        let mut order_tokens: Vec<OrderTokenResponse> = token_responses
+        .context("Failed to fetch detailed token information")?;
+
+        let order_tokens = token_responses
             .into_iter()
             .filter_map(|raw| {
                 let user_info: UserInfo = serde_json::from_value(raw.creator).ok()?;
@@ -248,20 +223,46 @@ impl OrderController {
                     description: raw.description.unwrap_or_default(),
                     reply_count: raw.reply_count.unwrap_or_default(),
                     price: raw.price.unwrap_or_default(),
+                    market_cap_usd: raw.market_cap.as_deref().and_then(market_cap_usd),
+                    market_cap: raw.market_cap.unwrap_or_default(),
                     user_info,
                     created_at: raw.created_at.unwrap_or_default(),
                 })
             })
             .collect();
 
-        //sort 를 뭘로 할지 정해져야함
-        order_tokens.sort_by(|a, b| {
-            let price_a: f64 = a.price.parse().unwrap_or(0.0);
-            let price_b: f64 = b.price.parse().unwrap_or(0.0);
-            price_b
-                .partial_cmp(&price_a)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
         Ok(order_tokens)
     }
+
+    pub async fn get_creation_time_order_token(&self) -> Result<Vec<TokenWithScore>> {
+        self.get_ordered_tokens(OrderType::CreationTime).await
+    }
+
+    pub async fn get_market_cap_order_token(&self) -> Result<Vec<TokenWithScore>> {
+        self.get_ordered_tokens(OrderType::MarketCap).await
+    }
+
+    pub async fn get_reply_count_order_token(&self) -> Result<Vec<TokenWithScore>> {
+        self.get_ordered_tokens(OrderType::ReplyCount).await
+    }
+
+    pub async fn get_latest_reply_order_token(&self) -> Result<Vec<TokenWithScore>> {
+        self.get_ordered_tokens(OrderType::LatestReply).await
+    }
+
+    /// Creation time of the token's newest thread, if it has any
+    pub async fn get_latest_reply_at(&self, token_id: &str) -> Result<Option<DateTime<Utc>>> {
+        let latest = sqlx::query_scalar!(
+            "SELECT MAX(created_at) FROM thread WHERE token_id = $1",
+            token_id
+        )
+        .fetch_one(&self.db.pool)
+        .await?;
+
+        Ok(latest)
+    }
+
+    pub async fn get_bump_order_token(&self) -> Result<Vec<TokenWithScore>> {
+        self.get_ordered_tokens(OrderType::Bump).await
+    }
 }
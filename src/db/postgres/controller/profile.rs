@@ -1,312 +1,566 @@
 use anyhow::Result;
 use bigdecimal::{BigDecimal, Zero};
+use futures::TryStreamExt;
 use sqlx::FromRow;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
     db::postgres::PostgresDatabase,
     types::{
-        model::{Account, Curve, Thread, Token},
-        profile::{HoldToken, Identifier},
+        model::{Account, Achievement, Curve, Swap, Thread, Token},
+        pagination::{Cursor, Pagination},
+        portfolio::{round_nad, PortfolioResponse, PortfolioToken, Position},
+        profile::{
+            CreatedTokenSort, FollowSort, HoldToken, HoldingSort, Identifier, UpdateProfileRequest,
+        },
+        thread::ThreadSort,
     },
 };
 
+use super::{
+    order::market_cap_sql,
+    thread::{CREATED_AT_KEY, LIKES_COUNT_KEY},
+};
+
 pub struct ProfileController {
     pub db: Arc<PostgresDatabase>,
 }
 #[derive(FromRow)]
 struct TokenHoldingRow {
-    // Token fields
-    id: String,
-    name: String,
-    symbol: String,
-    image_uri: String,
-    description: Option<String>,
-    creator: String,
-    created_at: i64,
-    twitter: Option<String>,
-    telegram: Option<String>,
-    website: Option<String>,
-    is_listing: bool,
-    create_transaction_hash: String,
-    is_updated: bool,
-    // Additional fields
+    #[sqlx(flatten)]
+    token: Token,
     balance: BigDecimal,
-    price: Option<BigDecimal>,
-    pair: Option<String>,
+    price: BigDecimal,
 }
 
 #[derive(FromRow)]
-struct TokenHoldingRecord {
+struct HeldTokenRow {
+    #[sqlx(flatten)]
+    holding: TokenHoldingRow,
+    balance_id: i32,
+    sort_key: BigDecimal,
+}
+
+#[derive(FromRow)]
+struct CreatedTokenRow {
+    #[sqlx(flatten)]
     token: Token,
-    balance: BigDecimal,
-    price: BigDecimal,
+    sort_key: BigDecimal,
+}
+
+#[derive(FromRow)]
+struct ReplyRow {
+    #[sqlx(flatten)]
+    thread: Thread,
+    sort_key: i64,
+}
+
+#[derive(FromRow)]
+struct FollowAccountRow {
+    #[sqlx(flatten)]
+    account: Account,
+    follow_id: i32,
+    sort_key: i64,
+}
+
+enum FollowSide {
+    Followers,
+    Following,
 }
 
 impl ProfileController {
     pub fn new(db: Arc<PostgresDatabase>) -> Self {
         ProfileController { db }
     }
-    pub async fn get_profile(&self, identifier: &Identifier) -> Result<Account> {
-        let account = match identifier {
-            Identifier::Nickname(nickname) => {
-                sqlx::query_as!(
-                    Account,
-                    "SELECT * FROM account WHERE nickname = $1",
-                    nickname
-                )
-                .fetch_one(&self.db.pool)
-                .await?
-            }
-            Identifier::Address(address) => {
-                sqlx::query_as!(Account, "SELECT * FROM account WHERE id = $1", address)
-                    .fetch_one(&self.db.pool)
-                    .await?
-            }
-        };
+    pub async fn get_profile(&self, account_id: &str) -> Result<Account> {
+        let account = sqlx::query_as!(Account, "SELECT * FROM account WHERE id = $1", account_id)
+            .fetch_one(&self.db.pool)
+            .await?;
 
         Ok(account)
     }
 
-    pub async fn get_holding_token(&self, identifier: &Identifier) -> Result<Vec<HoldToken>> {
-        let holdings = match identifier {
+    /// Case-insensitive, so look-alike nicknames can't impersonate each other
+    pub async fn is_nickname_taken(&self, nickname: &str, account_id: &str) -> Result<bool> {
+        let taken = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM account WHERE LOWER(nickname) = LOWER($1) AND id != $2
+            ) as "taken!"
+            "#,
+            nickname,
+            account_id
+        )
+        .fetch_one(&self.db.pool)
+        .await?;
+
+        Ok(taken)
+    }
+
+    pub async fn update_profile(
+        &self,
+        account_id: &str,
+        request: &UpdateProfileRequest,
+    ) -> Result<Account> {
+        let account = sqlx::query_as!(
+            Account,
+            r#"
+            UPDATE account
+            SET nickname = COALESCE($2, nickname),
+                bio = COALESCE($3, bio),
+                image_uri = COALESCE($4, image_uri)
+            WHERE id = $1
+            RETURNING *
+            "#,
+            account_id,
+            request.nickname,
+            request.bio,
+            request.image_uri
+        )
+        .fetch_one(&self.db.pool)
+        .await?;
+
+        Ok(account)
+    }
+
+    /// Case-insensitive like search and `is_nickname_taken`, so checksummed addresses resolve
+    pub async fn get_account_id(&self, identifier: &Identifier) -> Result<Option<String>> {
+        let account_id = match identifier {
             Identifier::Nickname(nickname) => {
-                sqlx::query_as!(
-                    TokenHoldingRow,
-                    r#"
-                    SELECT 
-                       t.*,
-                        b.amount AS balance,
-                        COALESCE(cu.price, 0) AS price
-                    FROM 
-                        balance b
-                    JOIN 
-                        account a ON b.account_id = a.id
-                    JOIN 
-                        token t ON b.token_id = t.id
-                    LEFT JOIN 
-                        curve cu ON t.id = cu.token_id
-                    WHERE 
-                        a.nickname = $1
-                    "#,
+                sqlx::query_scalar!(
+                    "SELECT id FROM account WHERE LOWER(nickname) = LOWER($1) ORDER BY id LIMIT 1",
                     nickname
                 )
-                .fetch_all(&self.db.pool)
+                .fetch_optional(&self.db.pool)
                 .await?
             }
             Identifier::Address(address) => {
-                sqlx::query_as!(
-                    TokenHoldingRow,
-                    r#"
-                    SELECT 
-                       t.*,
-                        b.amount AS balance,
-                        COALESCE(cu.price, 0) AS price
-                    FROM 
-                        balance b
-                    JOIN 
-                        token t ON b.token_id = t.id
-                    LEFT JOIN 
-                        curve cu ON t.id = cu.token_id
-                    WHERE 
-                        b.account_id = $1
-                    "#,
+                sqlx::query_scalar!(
+                    "SELECT id FROM account WHERE LOWER(id) = LOWER($1) ORDER BY id LIMIT 1",
                     address
                 )
-                .fetch_all(&self.db.pool)
+                .fetch_optional(&self.db.pool)
                 .await?
             }
         };
 
-        let mut results: Vec<TokenHoldingRecord> = holdings
+        Ok(account_id)
+    }
+
+    pub async fn get_achievements(&self, account_id: &str) -> Result<Vec<Achievement>> {
+        let achievements = sqlx::query_as!(
+            Achievement,
+            r#"
+            SELECT ac.id, ac.name, ac.image_uri
+            FROM account_achievement aa
+            JOIN achievement ac ON aa.achievement_id = ac.id
+            WHERE aa.account_id = $1
+            ORDER BY ac.id
+            "#,
+            account_id
+        )
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        Ok(achievements)
+    }
+
+    pub async fn get_holding_token(
+        &self,
+        account_id: &str,
+        sort: HoldingSort,
+        pagination: &Pagination<Cursor<BigDecimal>>,
+    ) -> Result<(Vec<HoldToken>, Option<String>)> {
+        let sort_key = match sort {
+            HoldingSort::Value => "b.amount * COALESCE(cu.price, 0)",
+            HoldingSort::Balance => "b.amount",
+        };
+        let query = format!(
+            r#"
+            SELECT
+                t.*,
+                b.amount AS balance,
+                COALESCE(cu.price, 0) AS price,
+                b.id AS balance_id,
+                {key} AS sort_key
+            FROM balance b
+            JOIN token t ON b.token_id = t.id
+            LEFT JOIN curve cu ON t.id = cu.token_id
+            WHERE b.account_id = $1
+                AND ($2::NUMERIC IS NULL OR ({key}, b.id) < ($2, $3))
+            ORDER BY sort_key DESC, b.id DESC
+            LIMIT $4
+            "#,
+            key = sort_key
+        );
+
+        let rows = sqlx::query_as::<_, HeldTokenRow>(&query)
+            .bind(account_id)
+            .bind(pagination.cursor.as_ref().map(|cursor| &cursor.key))
+            .bind(pagination.cursor.as_ref().map(|cursor| cursor.id))
+            .bind(pagination.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let next_cursor = pagination.next_cursor(
+            rows.len(),
+            rows.last().map(|row| Cursor {
+                key: row.sort_key.clone(),
+                id: row.balance_id,
+            }),
+        );
+        let holdings = rows
             .into_iter()
-            .map(|row| {
-                let token = Token {
-                    id: row.id,
-                    name: row.name,
-                    symbol: row.symbol,
-                    image_uri: row.image_uri,
-                    description: row.description,
-                    creator: row.creator,
-                    created_at: row.created_at,
-                    twitter: row.twitter,
-                    telegram: row.telegram,
-                    website: row.website,
-                    is_listing: row.is_listing,
-                    create_transaction_hash: row.create_transaction_hash,
-                    is_updated: row.is_updated,
-                    pair: row.pair,
-                };
-
-                TokenHoldingRecord {
-                    token,
-                    balance: row.balance,
-                    price: row.price.unwrap_or(BigDecimal::zero()),
-                }
+            .map(|row| HoldToken {
+                token: row.holding.token,
+                balance: row.holding.balance.to_string(),
+                price: row.holding.price.to_string(),
             })
             .collect();
 
-        results.sort_by(|a, b| {
-            let value_a = &a.price * &a.balance;
-            let value_b = &b.price * &b.balance;
-            value_b
-                .partial_cmp(&value_a)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        Ok((holdings, next_cursor))
+    }
 
-        let results: Vec<HoldToken> = results
+    /// Values every token the account holds or has swapped, with PnL from its swap history
+    pub async fn get_portfolio(&self, account_id: &str) -> Result<PortfolioResponse> {
+        let holdings = sqlx::query_as::<_, TokenHoldingRow>(
+            r#"
+            SELECT
+                t.*,
+                COALESCE(b.amount, 0) AS balance,
+                COALESCE(cu.price, 0) AS price
+            FROM token t
+            LEFT JOIN balance b ON b.token_id = t.id AND b.account_id = $1
+            LEFT JOIN curve cu ON t.id = cu.token_id
+            WHERE b.id IS NOT NULL
+                OR EXISTS (
+                    SELECT 1 FROM swap s WHERE s.token_id = t.id AND s.sender = $1
+                )
+            "#,
+        )
+        .bind(account_id)
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        // Swaps are folded as they stream in, so memory grows with the tokens held
+        // rather than with the account's trade history
+        let token_ids: Vec<String> = holdings.iter().map(|row| row.token.id.clone()).collect();
+        let mut swaps = sqlx::query_as!(
+            Swap,
+            r#"
+            SELECT *
+            FROM swap
+            WHERE sender = $1 AND token_id = ANY($2)
+            ORDER BY created_at, id
+            "#,
+            account_id,
+            &token_ids
+        )
+        .fetch(&self.db.pool);
+
+        let mut positions: HashMap<String, Position> = HashMap::new();
+        while let Some(swap) = swaps.try_next().await? {
+            positions
+                .entry(swap.token_id.clone())
+                .or_default()
+                .apply(&swap);
+        }
+
+        let mut tokens: Vec<PortfolioToken> = holdings
             .into_iter()
-            .map(|record| HoldToken {
-                token: record.token,
-                balance: record.balance.to_string(),
-                price: record.price.to_string(),
+            .map(|row| {
+                let position = positions.remove(&row.token.id).unwrap_or_default();
+                let value = round_nad(&row.balance * &row.price);
+                let cost_basis = round_nad(position.cost_basis(&row.balance));
+                PortfolioToken {
+                    unrealized_pnl: &value - &cost_basis,
+                    realized_pnl: round_nad(position.realized_pnl),
+                    token: row.token,
+                    balance: row.balance,
+                    price: row.price,
+                    value,
+                    cost_basis,
+                }
             })
             .collect();
+        tokens.sort_by(|a, b| b.value.cmp(&a.value));
+
+        let mut total_value = BigDecimal::zero();
+        let mut total_cost_basis = BigDecimal::zero();
+        let mut total_realized_pnl = BigDecimal::zero();
+        for token in &tokens {
+            total_value += &token.value;
+            total_cost_basis += &token.cost_basis;
+            total_realized_pnl += &token.realized_pnl;
+        }
 
-        Ok(results)
+        Ok(PortfolioResponse {
+            tokens,
+            total_unrealized_pnl: &total_value - &total_cost_basis,
+            total_value,
+            total_cost_basis,
+            total_realized_pnl,
+        })
     }
 
-    pub async fn get_replies(&self, identifier: &Identifier) -> Result<Vec<Thread>> {
-        let replies = match identifier {
-            Identifier::Nickname(nickname) => {
-                sqlx::query_as!(
-                    Thread,
-                    r#"
-                    SELECT t.*
-                    FROM thread t
-                    JOIN account a ON t.author_id = a.id
-                    WHERE a.nickname = $1
-                    ORDER BY t.created_at DESC
-                    "#,
-                    nickname
-                )
-                .fetch_all(&self.db.pool)
-                .await?
-            }
-            Identifier::Address(address) => {
-                sqlx::query_as!(
-                    Thread,
-                    r#"
-                    SELECT *
-                    FROM thread
-                    WHERE author_id = $1
-                    ORDER BY created_at DESC
-                    "#,
-                    address
-                )
-                .fetch_all(&self.db.pool)
-                .await?
-            }
+    pub async fn get_replies(
+        &self,
+        account_id: &str,
+        sort: ThreadSort,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Thread>, Option<String>)> {
+        let sort_key = match sort {
+            ThreadSort::Newest => CREATED_AT_KEY,
+            ThreadSort::MostLiked => LIKES_COUNT_KEY,
         };
+        let query = format!(
+            r#"
+            SELECT
+                t.*,
+                {key} as sort_key
+            FROM thread t
+            WHERE t.author_id = $1
+                AND ($2::BIGINT IS NULL OR ({key}, t.id) < ($2, $3))
+            ORDER BY sort_key DESC, t.id DESC
+            LIMIT $4
+            "#,
+            key = sort_key,
+        );
+
+        let rows = sqlx::query_as::<_, ReplyRow>(&query)
+            .bind(account_id)
+            .bind(pagination.cursor.map(|cursor| cursor.key))
+            .bind(pagination.cursor.map(|cursor| cursor.id))
+            .bind(pagination.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
 
-        Ok(replies)
+        let next_cursor = pagination.next_cursor(
+            rows.len(),
+            rows.last().map(|row| Cursor {
+                key: row.sort_key,
+                id: row.thread.id,
+            }),
+        );
+        let replies = rows.into_iter().map(|row| row.thread).collect();
+
+        Ok((replies, next_cursor))
     }
 
-    pub async fn get_created_tokens(&self, identifier: &Identifier) -> Result<Vec<Token>> {
-        let tokens = match identifier {
-            Identifier::Nickname(nickname) => {
-                sqlx::query_as!(
-                    Token,
-                    r#"
-                    SELECT t.*
-                    FROM token t
-                    JOIN account a ON t.creator = a.id
-                    WHERE a.nickname = $1
-                    ORDER BY t.created_at DESC
-                    "#,
-                    nickname
-                )
-                .fetch_all(&self.db.pool)
-                .await?
-            }
-            Identifier::Address(address) => {
-                sqlx::query_as!(
-                    Token,
-                    r#"
-                    SELECT *
-                    FROM token
-                    WHERE creator = $1
-                    ORDER BY created_at DESC
-                    "#,
-                    address
-                )
-                .fetch_all(&self.db.pool)
-                .await?
-            }
+    pub async fn get_created_tokens(
+        &self,
+        account_id: &str,
+        sort: CreatedTokenSort,
+        pagination: &Pagination<Cursor<BigDecimal, String>>,
+    ) -> Result<(Vec<Token>, Option<String>)> {
+        let sort_key = match sort {
+            CreatedTokenSort::Newest => "t.created_at::NUMERIC".to_string(),
+            CreatedTokenSort::MarketCap => format!("COALESCE({}, 0)", market_cap_sql("cu")),
         };
+        let query = format!(
+            r#"
+            SELECT
+                t.*,
+                {key} AS sort_key
+            FROM token t
+            LEFT JOIN curve cu ON t.id = cu.token_id
+            WHERE t.creator = $1
+                AND ($2::NUMERIC IS NULL OR ({key}, t.id) < ($2, $3))
+            ORDER BY sort_key DESC, t.id DESC
+            LIMIT $4
+            "#,
+            key = sort_key
+        );
 
-        Ok(tokens)
+        let rows = sqlx::query_as::<_, CreatedTokenRow>(&query)
+            .bind(account_id)
+            .bind(pagination.cursor.as_ref().map(|cursor| &cursor.key))
+            .bind(pagination.cursor.as_ref().map(|cursor| &cursor.id))
+            .bind(pagination.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let next_cursor = pagination.next_cursor(
+            rows.len(),
+            rows.last().map(|row| Cursor {
+                key: row.sort_key.clone(),
+                id: row.token.id.clone(),
+            }),
+        );
+        let tokens = rows.into_iter().map(|row| row.token).collect();
+
+        Ok((tokens, next_cursor))
     }
 
-    pub async fn get_followers(&self, identifier: &Identifier) -> Result<Vec<Account>> {
-        let followers = match identifier {
-            Identifier::Nickname(nickname) => {
-                sqlx::query_as!(
-                    Account,
-                    r#"
-                    SELECT a2.id, a2.nickname, a2.bio, a2.image_uri, a2.follower_count, a2.following_count, a2.like_count
-                    FROM follow f
-                    JOIN account a1 ON f.following_id = a1.id
-                    JOIN account a2 ON f.follower_id = a2.id
-                    WHERE a1.nickname = $1
-                    "#,
-                    nickname
-                )
-                .fetch_all(&self.db.pool)
-                .await?
-            },
-            Identifier::Address(address) => {
-                sqlx::query_as!(
-                    Account,
-                    r#"
-                    SELECT a.id, a.nickname, a.bio, a.image_uri, a.follower_count, a.following_count, a.like_count
-                    FROM follow f
-                    JOIN account a ON f.follower_id = a.id
-                    WHERE f.following_id = $1
-                    "#,
-                    address
-                )
-                .fetch_all(&self.db.pool)
-                .await?
-            }
+    pub async fn get_followers(
+        &self,
+        account_id: &str,
+        sort: FollowSort,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Account>, Option<String>)> {
+        self.get_follow_accounts(account_id, FollowSide::Followers, sort, pagination)
+            .await
+    }
+
+    pub async fn get_following(
+        &self,
+        account_id: &str,
+        sort: FollowSort,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Account>, Option<String>)> {
+        self.get_follow_accounts(account_id, FollowSide::Following, sort, pagination)
+            .await
+    }
+
+    async fn get_follow_accounts(
+        &self,
+        account_id: &str,
+        side: FollowSide,
+        sort: FollowSort,
+        pagination: &Pagination,
+    ) -> Result<(Vec<Account>, Option<String>)> {
+        // (listed account column, column matching the profile owner)
+        let (account_column, owner_column) = match side {
+            FollowSide::Followers => ("f.follower_id", "f.following_id"),
+            FollowSide::Following => ("f.following_id", "f.follower_id"),
+        };
+        let sort_key = match sort {
+            FollowSort::Newest => "f.id::BIGINT",
+            FollowSort::MostFollowers => "a.follower_count::BIGINT",
         };
+        let query = format!(
+            r#"
+            SELECT
+                a.id, a.nickname, a.bio, a.image_uri, a.follower_count, a.following_count, a.like_count,
+                f.id as follow_id,
+                {key} as sort_key
+            FROM follow f
+            JOIN account a ON {account_column} = a.id
+            WHERE {owner_column} = $1
+                AND ($2::BIGINT IS NULL OR ({key}, f.id) < ($2, $3))
+            ORDER BY sort_key DESC, f.id DESC
+            LIMIT $4
+            "#,
+            key = sort_key,
+            account_column = account_column,
+            owner_column = owner_column,
+        );
+
+        let rows = sqlx::query_as::<_, FollowAccountRow>(&query)
+            .bind(account_id)
+            .bind(pagination.cursor.map(|cursor| cursor.key))
+            .bind(pagination.cursor.map(|cursor| cursor.id))
+            .bind(pagination.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let next_cursor = pagination.next_cursor(
+            rows.len(),
+            rows.last().map(|row| Cursor {
+                key: row.sort_key,
+                id: row.follow_id,
+            }),
+        );
+        let accounts = rows.into_iter().map(|row| row.account).collect();
 
-        Ok(followers)
+        Ok((accounts, next_cursor))
     }
+}
 
-    pub async fn get_following(&self, identifier: &Identifier) -> Result<Vec<Account>> {
-        let followings = match identifier {
-            Identifier::Nickname(nickname) => {
-                sqlx::query_as!(
-                    Account,
-                    r#"
-                    SELECT a2.id, a2.nickname, a2.bio, a2.image_uri, a2.follower_count, a2.following_count, a2.like_count
-                    FROM follow f
-                    JOIN account a1 ON f.follower_id = a1.id
-                    JOIN account a2 ON f.following_id = a2.id
-                    WHERE a1.nickname = $1
-                    "#,
-                    nickname
-                )
-                .fetch_all(&self.db.pool)
-                .await?
-            },
-            Identifier::Address(address) => {
-                sqlx::query_as!(
-                    Account,
-                    r#"
-                    SELECT a.id, a.nickname, a.bio, a.image_uri, a.follower_count, a.following_count, a.like_count
-                    FROM follow f
-                    JOIN account a ON f.following_id = a.id
-                    WHERE f.follower_id = $1
-                    "#,
-                    address
-                )
-                .fetch_all(&self.db.pool)
-                .await?
-            }
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use super::*;
+
+    async fn seed(pool: &PgPool) {
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO account (id, nickname, image_uri) VALUES
+                ('0xalice', 'alice', '');
+            INSERT INTO token (id, name, symbol, image_uri, creator, is_listing, created_at, create_transaction_hash) VALUES
+                ('0xaaa', 'A', 'A', '', '0xalice', FALSE, 1, '0xt1'),
+                ('0xbbb', 'B', 'B', '', '0xalice', FALSE, 2, '0xt2'),
+                ('0xccc', 'C', 'C', '', '0xalice', FALSE, 2, '0xt3');
+            INSERT INTO balance (token_id, account_id, amount) VALUES
+                ('0xaaa', '0xalice', 300),
+                ('0xbbb', '0xalice', 200),
+                ('0xccc', '0xalice', 200);
+            INSERT INTO swap (sender, token_id, is_buy, nad_amount, token_amount, created_at, transaction_hash) VALUES
+                ('0xalice', '0xaaa', TRUE, 30, 300, 1, '0xs1');
+            "#,
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    fn ids(tokens: impl IntoIterator<Item = Token>) -> Vec<String> {
+        tokens.into_iter().map(|token| token.id).collect()
+    }
+
+    #[sqlx::test]
+    async fn held_tokens_are_paged_by_balance(pool: PgPool) {
+        seed(&pool).await;
+        let controller = ProfileController::new(Arc::new(PostgresDatabase { pool }));
+        let mut pagination = Pagination {
+            cursor: None,
+            limit: 2,
+        };
+
+        let (first, next_cursor) = controller
+            .get_holding_token("0xalice", HoldingSort::Balance, &pagination)
+            .await
+            .unwrap();
+        assert_eq!(
+            ids(first.into_iter().map(|holding| holding.token)),
+            vec!["0xaaa", "0xccc"]
+        );
+
+        pagination.cursor = next_cursor.map(|cursor| cursor.parse().unwrap());
+        let (second, next_cursor) = controller
+            .get_holding_token("0xalice", HoldingSort::Balance, &pagination)
+            .await
+            .unwrap();
+        assert_eq!(
+            ids(second.into_iter().map(|holding| holding.token)),
+            vec!["0xbbb"]
+        );
+        assert_eq!(next_cursor, None);
+    }
+
+    #[sqlx::test]
+    async fn created_tokens_are_paged_by_creation_time(pool: PgPool) {
+        seed(&pool).await;
+        let controller = ProfileController::new(Arc::new(PostgresDatabase { pool }));
+        let mut pagination = Pagination {
+            cursor: None,
+            limit: 2,
         };
 
-        Ok(followings)
+        let (first, next_cursor) = controller
+            .get_created_tokens("0xalice", CreatedTokenSort::Newest, &pagination)
+            .await
+            .unwrap();
+        assert_eq!(ids(first), vec!["0xccc", "0xbbb"]);
+        assert_eq!(next_cursor.as_deref(), Some("2_0xbbb"));
+
+        pagination.cursor = next_cursor.map(|cursor| cursor.parse().unwrap());
+        let (second, next_cursor) = controller
+            .get_created_tokens("0xalice", CreatedTokenSort::Newest, &pagination)
+            .await
+            .unwrap();
+        assert_eq!(ids(second), vec!["0xaaa"]);
+        assert_eq!(next_cursor, None);
+    }
+
+    #[sqlx::test]
+    async fn portfolio_values_every_holding(pool: PgPool) {
+        seed(&pool).await;
+        let controller = ProfileController::new(Arc::new(PostgresDatabase { pool }));
+
+        let portfolio = controller.get_portfolio("0xalice").await.unwrap();
+        assert_eq!(portfolio.tokens.len(), 3);
+        assert_eq!(portfolio.total_cost_basis, BigDecimal::from(30));
     }
 }
@@ -0,0 +1,175 @@
+use anyhow::Result;
+use std::sync::Arc;
+
+use crate::{
+    db::postgres::PostgresDatabase,
+    types::{
+        event::order::OrderTokenResponse,
+        model::Account,
+        profile::is_address,
+        search::{SearchFilter, SearchSort, TransactionHit, TransactionKind},
+    },
+};
+
+use super::order::{market_cap_sql, OrderController};
+
+/// Weight of the market cap percentile (0..1) in the relevance score
+const MARKET_CAP_WEIGHT: f64 = 0.3;
+/// Weight of the creation time percentile (0..1) in the relevance score
+const RECENCY_WEIGHT: f64 = 0.1;
+
+pub struct SearchController {
+    pub db: Arc<PostgresDatabase>,
+}
+
+impl SearchController {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        SearchController { db }
+    }
+
+    /// Matches tokens by name, symbol and description words (prefix), by name or symbol
+    /// similarity (typos) and by contract address prefix
+    pub async fn search_tokens(&self, filter: &SearchFilter) -> Result<Vec<OrderTokenResponse>> {
+        let order = match filter.sort {
+            SearchSort::Relevance => "score DESC, id",
+            SearchSort::MarketCap => "market_cap DESC, score DESC, id",
+            SearchSort::Newest => "created_at DESC, id",
+        };
+        let query = format!(
+            r#"
+            WITH matches AS (
+                SELECT
+                    t.id,
+                    t.created_at,
+                    COALESCE({market_cap}, 0) AS market_cap,
+                    COALESCE(
+                        ts_rank(
+                            token_search_vector(t.name, t.symbol, t.description),
+                            to_tsquery('simple', $2)
+                        ),
+                        0
+                    )
+                    + GREATEST(similarity(LOWER(t.name), $1), similarity(LOWER(t.symbol), $1))
+                    + CASE
+                        WHEN LOWER(t.symbol) = $1 OR LOWER(t.name) = $1 OR LOWER(t.id) = $1 THEN 1
+                        WHEN LOWER(t.symbol) LIKE $3 ESCAPE '\'
+                            OR LOWER(t.name) LIKE $3 ESCAPE '\'
+                            OR LOWER(t.id) LIKE $3 ESCAPE '\' THEN 0.5
+                        ELSE 0
+                    END AS relevance
+                FROM token t
+                LEFT JOIN curve cu ON t.id = cu.token_id
+                WHERE token_search_vector(t.name, t.symbol, t.description) @@ to_tsquery('simple', $2)
+                    OR LOWER(t.name) % $1
+                    OR LOWER(t.symbol) % $1
+                    OR LOWER(t.name) LIKE $3 ESCAPE '\'
+                    OR LOWER(t.symbol) LIKE $3 ESCAPE '\'
+                    OR LOWER(t.id) LIKE $3 ESCAPE '\'
+            ),
+            scored AS (
+                SELECT
+                    id,
+                    created_at,
+                    market_cap,
+                    relevance
+                        + $5 * PERCENT_RANK() OVER (ORDER BY market_cap)
+                        + $6 * PERCENT_RANK() OVER (ORDER BY created_at) AS score
+                FROM matches
+            )
+            SELECT id
+            FROM scored
+            ORDER BY {order}
+            LIMIT $4
+            "#,
+            market_cap = market_cap_sql("cu"),
+            order = order
+        );
+
+        let ids: Vec<String> = sqlx::query_scalar(&query)
+            .bind(&filter.term)
+            .bind(filter.prefix_tsquery())
+            .bind(filter.like_prefix())
+            .bind(filter.limit)
+            .bind(MARKET_CAP_WEIGHT)
+            .bind(RECENCY_WEIGHT)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let order_controller = OrderController::new(self.db.clone());
+        let mut tokens = order_controller.get_order_token_responses(&ids).await?;
+        tokens.sort_by_key(|token| ids.iter().position(|id| *id == token.id));
+
+        Ok(tokens)
+    }
+
+    /// Exact address match for address-shaped terms, nickname prefix or similarity otherwise
+    pub async fn search_accounts(&self, filter: &SearchFilter) -> Result<Vec<Account>> {
+        if is_address(&filter.term) {
+            let accounts = sqlx::query_as!(
+                Account,
+                "SELECT * FROM account WHERE LOWER(id) = $1",
+                filter.term
+            )
+            .fetch_all(&self.db.pool)
+            .await?;
+            return Ok(accounts);
+        }
+
+        let accounts = sqlx::query_as!(
+            Account,
+            r#"
+            SELECT *
+            FROM account
+            WHERE LOWER(nickname) LIKE $2 ESCAPE '\'
+                OR LOWER(nickname) % $1
+            ORDER BY
+                LOWER(nickname) = $1 DESC,
+                LOWER(nickname) LIKE $2 ESCAPE '\' DESC,
+                similarity(LOWER(nickname), $1) DESC,
+                follower_count DESC,
+                id
+            LIMIT $3
+            "#,
+            filter.term,
+            filter.like_prefix(),
+            filter.limit
+        )
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        Ok(accounts)
+    }
+
+    /// Swaps and token creations sent in the transaction `hash` (lowercase)
+    pub async fn search_transactions(&self, hash: &str) -> Result<Vec<TransactionHit>> {
+        let swaps = sqlx::query!(
+            "SELECT * FROM swap WHERE LOWER(transaction_hash) = $1",
+            hash
+        )
+        .fetch_all(&self.db.pool)
+        .await?;
+        let tokens = sqlx::query!(
+            "SELECT * FROM token WHERE LOWER(create_transaction_hash) = $1",
+            hash
+        )
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        let creations = tokens.into_iter().map(|token| TransactionHit {
+            kind: TransactionKind::TokenCreation,
+            transaction_hash: token.create_transaction_hash,
+            token_id: token.id,
+            account_id: token.creator,
+            created_at: token.created_at,
+        });
+        let swaps = swaps.into_iter().map(|swap| TransactionHit {
+            kind: TransactionKind::Swap,
+            transaction_hash: swap.transaction_hash,
+            token_id: swap.token_id,
+            account_id: swap.sender,
+            created_at: swap.created_at,
+        });
+
+        Ok(creations.chain(swaps).collect())
+    }
+}
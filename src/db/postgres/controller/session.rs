@@ -0,0 +1,81 @@
+use anyhow::Result;
+
+use std::sync::Arc;
+
+use crate::{db::postgres::PostgresDatabase, types::model::AccountSession};
+
+pub struct SessionController {
+    pub db: Arc<PostgresDatabase>,
+}
+
+impl SessionController {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        SessionController { db }
+    }
+
+    pub async fn get_session(&self, session_id: &str) -> Result<Option<AccountSession>> {
+        let session = sqlx::query_as!(
+            AccountSession,
+            "SELECT id, account_id FROM account_session WHERE id = $1",
+            session_id
+        )
+        .fetch_optional(&self.db.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    /// Creates the account on first sign-in and replaces any previous session.
+    /// `address` is matched case-insensitively, so an account stored in checksummed
+    /// case is reused; returns the stored account id.
+    pub async fn create_session(&self, address: &str, session_id: &str) -> Result<String> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let existing = sqlx::query_scalar!(
+            "SELECT id FROM account WHERE LOWER(id) = LOWER($1) ORDER BY id LIMIT 1",
+            address
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let account_id = match existing {
+            Some(account_id) => account_id,
+            None => {
+                let account_id = address.to_lowercase();
+                sqlx::query!(
+                    r#"
+                    INSERT INTO account (id, nickname, image_uri)
+                    VALUES ($1, $1, '')
+                    ON CONFLICT (id) DO NOTHING
+                    "#,
+                    account_id
+                )
+                .execute(&mut *tx)
+                .await?;
+                account_id
+            }
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO account_session (id, account_id)
+            VALUES ($1, $2)
+            ON CONFLICT (account_id) DO UPDATE SET id = EXCLUDED.id
+            "#,
+            session_id,
+            account_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(account_id)
+    }
+
+    pub async fn delete_session(&self, session_id: &str) -> Result<()> {
+        sqlx::query!("DELETE FROM account_session WHERE id = $1", session_id)
+            .execute(&self.db.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,362 @@
+use anyhow::Result;
+use sqlx::FromRow;
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    db::postgres::PostgresDatabase,
+    types::{
+        event::UserInfo,
+        model::Thread,
+        pagination::Cursor,
+        thread::{
+            CreateThreadRequest, RootThread, ThreadFilter, ThreadPost, ThreadRepliesResponse,
+            ThreadSort, ThreadsResponse, UpdateThreadRequest,
+        },
+    },
+};
+
+/// `thread.created_at` in microseconds, used as the keyset for chronological order
+pub(crate) const CREATED_AT_KEY: &str = "(EXTRACT(EPOCH FROM t.created_at) * 1000000)::BIGINT";
+pub(crate) const LIKES_COUNT_KEY: &str = "t.likes_count::BIGINT";
+
+pub struct ThreadController {
+    pub db: Arc<PostgresDatabase>,
+}
+
+#[derive(FromRow)]
+struct ThreadRow {
+    #[sqlx(flatten)]
+    thread: Thread,
+    author_nickname: String,
+    author_image_uri: String,
+    sort_key: i64,
+}
+
+impl ThreadRow {
+    fn cursor(&self) -> String {
+        Cursor {
+            key: self.sort_key,
+            id: self.thread.id,
+        }
+        .to_string()
+    }
+
+    fn into_post(self) -> ThreadPost {
+        ThreadPost {
+            thread: self.thread,
+            author: UserInfo {
+                nickname: self.author_nickname,
+                image_uri: self.author_image_uri,
+            },
+        }
+    }
+}
+
+impl ThreadController {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        ThreadController { db }
+    }
+
+    pub async fn get_thread(&self, thread_id: i32) -> Result<Option<Thread>> {
+        let thread = sqlx::query_as!(Thread, "SELECT * FROM thread WHERE id = $1", thread_id)
+            .fetch_optional(&self.db.pool)
+            .await?;
+
+        Ok(thread)
+    }
+
+    pub async fn get_token_threads(
+        &self,
+        token_id: &str,
+        filter: &ThreadFilter,
+    ) -> Result<ThreadsResponse> {
+        let sort_key = match filter.sort {
+            ThreadSort::Newest => CREATED_AT_KEY,
+            ThreadSort::MostLiked => LIKES_COUNT_KEY,
+        };
+        let query = format!(
+            r#"
+            SELECT
+                t.*,
+                a.nickname as author_nickname,
+                a.image_uri as author_image_uri,
+                {key} as sort_key
+            FROM thread t
+            JOIN account a ON t.author_id = a.id
+            WHERE t.token_id = $1
+                AND t.root_id IS NULL
+                AND ($2::BIGINT IS NULL OR ({key}, t.id) < ($2, $3))
+            ORDER BY sort_key DESC, t.id DESC
+            LIMIT $4
+            "#,
+            key = sort_key
+        );
+
+        let roots = sqlx::query_as::<_, ThreadRow>(&query)
+            .bind(token_id)
+            .bind(filter.cursor.map(|cursor| cursor.key))
+            .bind(filter.cursor.map(|cursor| cursor.id))
+            .bind(filter.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let next_cursor = if roots.len() as i64 == filter.limit {
+            roots.last().map(ThreadRow::cursor)
+        } else {
+            None
+        };
+
+        let mut replies = self
+            .get_reply_previews(
+                &roots.iter().map(|root| root.thread.id).collect::<Vec<_>>(),
+                filter.reply_limit,
+            )
+            .await?;
+
+        let threads = roots
+            .into_iter()
+            .map(|root| {
+                let (replies, next_reply_cursor) =
+                    replies.remove(&root.thread.id).unwrap_or_default();
+                let ThreadPost { thread, author } = root.into_post();
+                RootThread {
+                    thread,
+                    author,
+                    replies,
+                    next_reply_cursor,
+                }
+            })
+            .collect();
+
+        Ok(ThreadsResponse {
+            threads,
+            next_cursor,
+        })
+    }
+
+    /// Fetches the first `limit` replies of every root thread, keyed by root id.
+    async fn get_reply_previews(
+        &self,
+        root_ids: &[i32],
+        limit: i64,
+    ) -> Result<HashMap<i32, (Vec<ThreadPost>, Option<String>)>> {
+        let mut previews = HashMap::new();
+        if root_ids.is_empty() || limit == 0 {
+            return Ok(previews);
+        }
+
+        let query = format!(
+            r#"
+            SELECT * FROM (
+                SELECT
+                    t.*,
+                    a.nickname as author_nickname,
+                    a.image_uri as author_image_uri,
+                    {key} as sort_key,
+                    ROW_NUMBER() OVER (PARTITION BY t.root_id ORDER BY t.created_at, t.id) as rn
+                FROM thread t
+                JOIN account a ON t.author_id = a.id
+                WHERE t.root_id = ANY($1)
+            ) r
+            WHERE rn <= $2
+            ORDER BY created_at, id
+            "#,
+            key = CREATED_AT_KEY
+        );
+
+        // One extra row per root tells whether more replies exist
+        let rows = sqlx::query_as::<_, ThreadRow>(&query)
+            .bind(root_ids)
+            .bind(limit + 1)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let mut grouped: HashMap<i32, Vec<ThreadRow>> = HashMap::new();
+        for row in rows {
+            if let Some(root_id) = row.thread.root_id {
+                grouped.entry(root_id).or_default().push(row);
+            }
+        }
+
+        for (root_id, mut rows) in grouped {
+            let next_cursor = if rows.len() as i64 > limit {
+                rows.truncate(limit as usize);
+                rows.last().map(ThreadRow::cursor)
+            } else {
+                None
+            };
+            let replies = rows.into_iter().map(ThreadRow::into_post).collect();
+            previews.insert(root_id, (replies, next_cursor));
+        }
+
+        Ok(previews)
+    }
+
+    pub async fn get_thread_replies(
+        &self,
+        root_id: i32,
+        cursor: Option<Cursor>,
+        limit: i64,
+    ) -> Result<ThreadRepliesResponse> {
+        let query = format!(
+            r#"
+            SELECT
+                t.*,
+                a.nickname as author_nickname,
+                a.image_uri as author_image_uri,
+                {key} as sort_key
+            FROM thread t
+            JOIN account a ON t.author_id = a.id
+            WHERE t.root_id = $1
+                AND ($2::BIGINT IS NULL OR ({key}, t.id) > ($2, $3))
+            ORDER BY t.created_at, t.id
+            LIMIT $4
+            "#,
+            key = CREATED_AT_KEY
+        );
+
+        let rows = sqlx::query_as::<_, ThreadRow>(&query)
+            .bind(root_id)
+            .bind(cursor.map(|cursor| cursor.key))
+            .bind(cursor.map(|cursor| cursor.id))
+            .bind(limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let next_cursor = if rows.len() as i64 == limit {
+            rows.last().map(ThreadRow::cursor)
+        } else {
+            None
+        };
+
+        Ok(ThreadRepliesResponse {
+            replies: rows.into_iter().map(ThreadRow::into_post).collect(),
+            next_cursor,
+        })
+    }
+
+    /// Inserts the thread and bumps the root's and the token's reply counts in one
+    /// transaction; the `thread_change` and `new_token_reply` triggers fan it out.
+    pub async fn create_thread(
+        &self,
+        token_id: &str,
+        author_id: &str,
+        request: &CreateThreadRequest,
+    ) -> Result<Thread> {
+        let mut tx = self.db.pool.begin().await?;
+
+        let thread = sqlx::query_as!(
+            Thread,
+            r#"
+            INSERT INTO thread (token_id, author_id, content, root_id, image_uri)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+            token_id,
+            author_id,
+            request.content,
+            request.root_id,
+            request.image_uri
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(root_id) = thread.root_id {
+            sqlx::query!(
+                "UPDATE thread SET reply_count = reply_count + 1 WHERE id = $1",
+                root_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO token_reply_count (token_id, reply_count)
+            VALUES ($1, 1)
+            ON CONFLICT (token_id) DO UPDATE
+            SET reply_count = token_reply_count.reply_count + 1
+            "#,
+            token_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(thread)
+    }
+
+    pub async fn update_thread(
+        &self,
+        thread_id: i32,
+        request: &UpdateThreadRequest,
+    ) -> Result<Thread> {
+        let thread = sqlx::query_as!(
+            Thread,
+            r#"
+            UPDATE thread
+            SET content = COALESCE($2, content),
+                image_uri = COALESCE($3, image_uri),
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING *
+            "#,
+            thread_id,
+            request.content,
+            request.image_uri
+        )
+        .fetch_one(&self.db.pool)
+        .await?;
+
+        Ok(thread)
+    }
+
+    /// Deletes the thread together with its replies and likes, keeping the reply counts in step
+    pub async fn delete_thread(&self, thread: &Thread) -> Result<()> {
+        let mut tx = self.db.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM thread_likes
+            WHERE thread_id IN (SELECT id FROM thread WHERE id = $1 OR root_id = $1)
+            "#,
+            thread.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let deleted_replies = sqlx::query!("DELETE FROM thread WHERE root_id = $1", thread.id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected() as i32;
+
+        sqlx::query!("DELETE FROM thread WHERE id = $1", thread.id)
+            .execute(&mut *tx)
+            .await?;
+
+        if let Some(root_id) = thread.root_id {
+            sqlx::query!(
+                "UPDATE thread SET reply_count = reply_count - 1 WHERE id = $1",
+                root_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE token_reply_count
+            SET reply_count = GREATEST(reply_count - $2, 0)
+            WHERE token_id = $1
+            "#,
+            thread.token_id,
+            deleted_replies + 1
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,409 @@
+use anyhow::Result;
+use bigdecimal::{BigDecimal, Zero};
+
+use std::sync::Arc;
+
+use crate::{
+    db::postgres::PostgresDatabase,
+    types::{
+        chart_type::ChartType,
+        event::UserInfo,
+        model::{Chart, Curve, Token},
+        token::{HolderStats, HoldersResponse, TokenDetail, TokenHolder, UpdateTokenRequest},
+    },
+};
+
+pub const CHART_LIMIT: i64 = 300;
+pub const MAX_CHART_LIMIT: i64 = 1000;
+
+pub struct TokenController {
+    pub db: Arc<PostgresDatabase>,
+}
+
+impl TokenController {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        TokenController { db }
+    }
+
+    pub async fn get_token(&self, token_id: &str) -> Result<Option<Token>> {
+        let token = sqlx::query_as!(Token, "SELECT * FROM token WHERE id = $1", token_id)
+            .fetch_optional(&self.db.pool)
+            .await?;
+
+        Ok(token)
+    }
+
+    /// Applies the creator's one-time metadata edit. Returns `None` if the token
+    /// doesn't exist, isn't theirs or was already edited.
+    pub async fn update_token(
+        &self,
+        token_id: &str,
+        creator: &str,
+        request: &UpdateTokenRequest,
+    ) -> Result<Option<Token>> {
+        let token = sqlx::query_as!(
+            Token,
+            r#"
+            UPDATE token
+            SET description = CASE WHEN $3::TEXT IS NULL THEN description ELSE NULLIF($3, '') END,
+                twitter = CASE WHEN $4::VARCHAR IS NULL THEN twitter ELSE NULLIF($4, '') END,
+                telegram = CASE WHEN $5::VARCHAR IS NULL THEN telegram ELSE NULLIF($5, '') END,
+                website = CASE WHEN $6::VARCHAR IS NULL THEN website ELSE NULLIF($6, '') END,
+                is_updated = TRUE
+            WHERE id = $1 AND creator = $2 AND NOT is_updated
+            RETURNING *
+            "#,
+            token_id,
+            creator,
+            request.description,
+            request.twitter,
+            request.telegram,
+            request.website
+        )
+        .fetch_optional(&self.db.pool)
+        .await?;
+
+        Ok(token)
+    }
+
+    pub async fn get_token_detail(&self, token_id: &str) -> Result<Option<TokenDetail>> {
+        let Some(token) = self.get_token(token_id).await? else {
+            return Ok(None);
+        };
+
+        let curve = sqlx::query_as!(Curve, "SELECT * FROM curve WHERE token_id = $1", token_id)
+            .fetch_optional(&self.db.pool)
+            .await?;
+
+        let creator = sqlx::query_as!(
+            UserInfo,
+            "SELECT nickname, image_uri FROM account WHERE id = $1",
+            token.creator
+        )
+        .fetch_optional(&self.db.pool)
+        .await?;
+
+        let counts = sqlx::query!(
+            r#"
+            SELECT
+                COALESCE((SELECT reply_count FROM token_reply_count WHERE token_id = $1), 0) as "reply_count!"
+            "#,
+            token_id
+        )
+        .fetch_one(&self.db.pool)
+        .await?;
+
+        let holder_count = self.get_holder_count(token_id).await?;
+
+        Ok(Some(TokenDetail {
+            token,
+            curve,
+            creator,
+            reply_count: counts.reply_count,
+            holder_count,
+        }))
+    }
+
+    /// Counts the accounts holding a positive balance of the token. The curve
+    /// contract's own balance is not a holder.
+    pub async fn get_holder_count(&self, token_id: &str) -> Result<i64> {
+        let holder_count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "holder_count!"
+            FROM balance b
+            LEFT JOIN curve cu ON cu.token_id = b.token_id AND cu.id = b.account_id
+            WHERE b.token_id = $1 AND b.amount > 0 AND cu.id IS NULL
+            "#,
+            token_id
+        )
+        .fetch_one(&self.db.pool)
+        .await?;
+
+        Ok(holder_count)
+    }
+
+    /// Returns `None` if the token doesn't exist
+    pub async fn get_holders(&self, token_id: &str, limit: i64) -> Result<Option<HoldersResponse>> {
+        if self.get_token(token_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let stats = sqlx::query!(
+            r#"
+            WITH holder AS (
+                SELECT b.amount, ROW_NUMBER() OVER (ORDER BY b.amount DESC) as rank
+                FROM balance b
+                LEFT JOIN curve cu ON cu.token_id = b.token_id AND cu.id = b.account_id
+                WHERE b.token_id = $1 AND b.amount > 0 AND cu.id IS NULL
+            )
+            SELECT
+                (SELECT COALESCE(SUM(amount), 0) FROM balance WHERE token_id = $1) as "total_supply!",
+                (SELECT COALESCE(SUM(amount), 0) FROM holder WHERE rank <= 10) as "top10_amount!"
+            "#,
+            token_id
+        )
+        .fetch_one(&self.db.pool)
+        .await?;
+
+        let holders = sqlx::query!(
+            r#"
+            SELECT
+                b.account_id,
+                b.amount,
+                a.nickname as "nickname?",
+                a.image_uri as "image_uri?",
+                b.account_id = t.creator as "is_creator!",
+                cu.id IS NOT NULL as "is_curve!"
+            FROM balance b
+            LEFT JOIN account a ON b.account_id = a.id
+            JOIN token t ON b.token_id = t.id
+            LEFT JOIN curve cu ON cu.token_id = b.token_id AND cu.id = b.account_id
+            WHERE b.token_id = $1 AND b.amount > 0
+            ORDER BY b.amount DESC
+            LIMIT $2
+            "#,
+            token_id,
+            limit
+        )
+        .fetch_all(&self.db.pool)
+        .await?;
+
+        let percentage = |amount: &BigDecimal| {
+            if stats.total_supply.is_zero() {
+                BigDecimal::zero()
+            } else {
+                (amount * BigDecimal::from(100) / &stats.total_supply).round(4)
+            }
+        };
+
+        let holders = holders
+            .into_iter()
+            .map(|row| TokenHolder {
+                percentage: percentage(&row.amount).to_string(),
+                account_id: row.account_id,
+                user_info: row
+                    .nickname
+                    .zip(row.image_uri)
+                    .map(|(nickname, image_uri)| UserInfo {
+                        nickname,
+                        image_uri,
+                    }),
+                amount: row.amount.to_string(),
+                is_creator: row.is_creator,
+                is_curve: row.is_curve,
+            })
+            .collect();
+
+        let holder_count = self.get_holder_count(token_id).await?;
+
+        Ok(Some(HoldersResponse {
+            holders,
+            stats: HolderStats {
+                holder_count,
+                top10_percentage: percentage(&stats.top10_amount).to_string(),
+                total_supply: stats.total_supply.to_string(),
+            },
+        }))
+    }
+
+    /// Returns up to `limit` of the most recent candles in `[from, to)`, ordered by `time_stamp` ascending.
+    pub async fn get_charts(
+        &self,
+        token_id: &str,
+        chart_type: &ChartType,
+        from: Option<i64>,
+        to: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<Chart>> {
+        let query = format!(
+            r#"
+            SELECT * FROM (
+                SELECT *
+                FROM {}
+                WHERE token_id = $1
+                    AND ($2::BIGINT IS NULL OR time_stamp >= $2)
+                    AND ($3::BIGINT IS NULL OR time_stamp < $3)
+                ORDER BY time_stamp DESC
+                LIMIT $4
+            ) ch
+            ORDER BY time_stamp ASC
+            "#,
+            chart_type.table_name()
+        );
+
+        let charts = sqlx::query_as::<_, Chart>(&query)
+            .bind(token_id)
+            .bind(from)
+            .bind(to)
+            .bind(limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        Ok(charts)
+    }
+}
+
+fn flat_candle(prev: &Chart, time_stamp: i64) -> Chart {
+    Chart {
+        id: 0,
+        token_id: prev.token_id.clone(),
+        open_price: prev.close_price.clone(),
+        close_price: prev.close_price.clone(),
+        high_price: prev.close_price.clone(),
+        low_price: prev.close_price.clone(),
+        time_stamp,
+        nad_volume: BigDecimal::zero(),
+        token_volume: BigDecimal::zero(),
+        trade_count: 0,
+    }
+}
+
+/// Inserts a flat candle at the previous close for every empty bucket between `charts`
+/// and after the last one up to `until` (exclusive), so consecutive pages join up.
+///
+/// Keeps the newest `limit` buckets. When older candles are dropped, the result starts at
+/// its oldest real candle if it has one, since the next page fills up to that cursor.
+/// Returns whether anything was dropped.
+pub fn fill_chart_gaps(
+    charts: Vec<Chart>,
+    interval: i64,
+    until: Option<i64>,
+    limit: usize,
+) -> (Vec<Chart>, bool) {
+    let mut filled: Vec<Chart> = Vec::with_capacity(charts.len());
+    let mut dropped = false;
+    let mut fill_until = |filled: &mut Vec<Chart>, end: i64| {
+        let Some(prev) = filled.last().cloned() else {
+            return;
+        };
+        let mut time_stamp = prev.time_stamp + interval;
+        // Only the newest `limit` buckets of a long gap can be kept
+        let buckets = (end - time_stamp + interval - 1) / interval;
+        if buckets > limit as i64 {
+            time_stamp += (buckets - limit as i64) * interval;
+            dropped = true;
+        }
+        while time_stamp < end {
+            filled.push(flat_candle(&prev, time_stamp));
+            time_stamp += interval;
+        }
+    };
+
+    for chart in charts {
+        fill_until(&mut filled, chart.time_stamp);
+        filled.push(chart);
+    }
+    if let Some(until) = until {
+        fill_until(&mut filled, until);
+    }
+
+    let overflow = filled.len().saturating_sub(limit);
+    if overflow > 0 || dropped {
+        filled.drain(..overflow);
+        if let Some(first_real) = filled.iter().position(|chart| chart.id != 0) {
+            filled.drain(..first_real);
+        }
+        dropped = true;
+    }
+    (filled, dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: i64 = 60;
+
+    fn candle(id: i32, time_stamp: i64, close_price: i64) -> Chart {
+        Chart {
+            id,
+            token_id: "0xtoken".into(),
+            open_price: BigDecimal::from(close_price),
+            close_price: BigDecimal::from(close_price),
+            high_price: BigDecimal::from(close_price),
+            low_price: BigDecimal::from(close_price),
+            time_stamp,
+            nad_volume: BigDecimal::from(1),
+            token_volume: BigDecimal::from(1),
+            trade_count: 1,
+        }
+    }
+
+    fn time_stamps(charts: &[Chart]) -> Vec<i64> {
+        charts.iter().map(|chart| chart.time_stamp).collect()
+    }
+
+    #[test]
+    fn fills_between_candles_and_up_to_cursor() {
+        let charts = vec![candle(1, 0, 10), candle(2, 180, 20)];
+
+        let (filled, dropped) = fill_chart_gaps(charts, INTERVAL, Some(360), 100);
+
+        assert!(!dropped);
+        assert_eq!(time_stamps(&filled), vec![0, 60, 120, 180, 240, 300]);
+        assert_eq!(filled[1].close_price, BigDecimal::from(10));
+        assert_eq!(filled[1].trade_count, 0);
+        assert_eq!(filled[5].close_price, BigDecimal::from(20));
+    }
+
+    #[test]
+    fn starts_trimmed_page_at_real_candle() {
+        let charts = vec![candle(1, 0, 10), candle(2, 60, 20), candle(3, 300, 30)];
+
+        let (filled, dropped) = fill_chart_gaps(charts, INTERVAL, None, 4);
+
+        assert!(dropped);
+        // 120..240 are flat candles after 60, which leads the next page
+        assert_eq!(time_stamps(&filled), vec![300]);
+    }
+
+    #[test]
+    fn bounds_long_gap_to_limit() {
+        let charts = vec![candle(1, 0, 10)];
+
+        let (filled, dropped) = fill_chart_gaps(charts, INTERVAL, Some(INTERVAL * 1_000_000), 3);
+
+        assert!(dropped);
+        assert_eq!(
+            time_stamps(&filled),
+            vec![INTERVAL * 999_997, INTERVAL * 999_998, INTERVAL * 999_999]
+        );
+    }
+
+    #[test]
+    fn pages_join_without_holes() {
+        let history = [
+            candle(1, 0, 10),
+            candle(2, 240, 20),
+            candle(3, 300, 30),
+            candle(4, 600, 40),
+            candle(5, 900, 50),
+        ];
+        let limit = 3;
+
+        // Mirrors `get_charts`: the newest `limit` real candles before `to`, oldest first
+        let mut to = None;
+        let mut pages = Vec::new();
+        loop {
+            let before: Vec<Chart> = history
+                .iter()
+                .filter(|chart| to.is_none_or(|to| chart.time_stamp < to))
+                .cloned()
+                .collect();
+            let page = before[before.len().saturating_sub(limit)..].to_vec();
+            let has_more = page.len() == limit;
+            let (filled, dropped) = fill_chart_gaps(page, INTERVAL, to, limit);
+            to = filled.first().map(|chart| chart.time_stamp);
+            pages.insert(0, filled);
+            if !(has_more || dropped) {
+                break;
+            }
+        }
+
+        let joined: Vec<Chart> = pages.into_iter().flatten().collect();
+        assert_eq!(
+            time_stamps(&joined),
+            (0..=900).step_by(60).collect::<Vec<i64>>()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::db::postgres::controller::token::CHART_LIMIT;
 use crate::types::model::{Balance, BalanceWrapper, Chart, ChartWrapper, Thread, ThreadWrapper};
 use crate::{
     db::postgres::PostgresDatabase,
@@ -29,21 +30,15 @@ impl TokenPageController {
         token_id: &str,
         chart_type: ChartType,
     ) -> Result<TokenResponse> {
-        let chart_table = match chart_type {
-            ChartType::OneMinute => "chart_1m",
-            ChartType::FiveMinutes => "chart_5m",
-            ChartType::FifteenMinutes => "chart_15m",
-            ChartType::ThirtyMinutes => "chart_30m",
-            ChartType::OneHour => "chart_1h",
-            ChartType::FourHours => "chart_4h",
-            ChartType::OneDay => "chart_1d",
-        };
+        let chart_table = chart_type.table_name();
 
         let query = format!(
             r#"
             SELECT 
                 (SELECT json_agg(row_to_json(s)) FROM swap s WHERE s.token_id = $1) as swap,
-                (SELECT json_agg(row_to_json(ch)) FROM {} ch WHERE ch.token_id = $1) as chart,
+                (SELECT json_agg(row_to_json(ch) ORDER BY ch.time_stamp) FROM (
+                    SELECT * FROM {} WHERE token_id = $1 ORDER BY time_stamp DESC LIMIT $2
+                ) ch) as chart,
                 (SELECT json_agg(row_to_json(b)) FROM balance b WHERE b.token_id = $1) as balance,
                 (SELECT row_to_json(cu) FROM curve cu WHERE cu.token_id = $1 LIMIT 1) as curve,
                 (SELECT json_agg(row_to_json(t)) FROM thread t WHERE t.token_id = $1) as thread
@@ -53,6 +48,7 @@ impl TokenPageController {
 
         let raw = sqlx::query_as::<_, TokenResponseRaw>(&query)
             .bind(token_id)
+            .bind(CHART_LIMIT)
             .fetch_one(&self.db.pool)
             .await
             .context("Failed to fetch token data")?;
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use sqlx::FromRow;
+
+use std::sync::Arc;
+
+use crate::{
+    db::postgres::PostgresDatabase,
+    types::{
+        event::UserInfo,
+        model::Swap,
+        pagination::Cursor,
+        trade::{Trade, TradeFilter, TradesResponse},
+    },
+};
+
+pub struct TradeController {
+    pub db: Arc<PostgresDatabase>,
+}
+
+#[derive(FromRow)]
+struct TradeRow {
+    #[sqlx(flatten)]
+    swap: Swap,
+    user_nickname: String,
+    user_image_uri: String,
+}
+
+impl TradeController {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        TradeController { db }
+    }
+
+    pub async fn get_token_trades(
+        &self,
+        token_id: &str,
+        filter: &TradeFilter,
+    ) -> Result<TradesResponse> {
+        self.get_trades("s.token_id = $1", token_id, filter).await
+    }
+
+    pub async fn get_account_trades(
+        &self,
+        account_id: &str,
+        filter: &TradeFilter,
+    ) -> Result<TradesResponse> {
+        self.get_trades("s.sender = $1", account_id, filter).await
+    }
+
+    async fn get_trades(
+        &self,
+        condition: &str,
+        value: &str,
+        filter: &TradeFilter,
+    ) -> Result<TradesResponse> {
+        let query = format!(
+            r#"
+            SELECT
+                s.*,
+                a.nickname as user_nickname,
+                a.image_uri as user_image_uri
+            FROM swap s
+            JOIN account a ON s.sender = a.id
+            WHERE {}
+                AND ($2::BOOLEAN IS NULL OR s.is_buy = $2)
+                AND ($3::NUMERIC IS NULL OR s.nad_amount >= $3)
+                AND ($4::BIGINT IS NULL OR (s.created_at, s.id) < ($4, $5))
+            ORDER BY s.created_at DESC, s.id DESC
+            LIMIT $6
+            "#,
+            condition
+        );
+
+        let rows = sqlx::query_as::<_, TradeRow>(&query)
+            .bind(value)
+            .bind(filter.is_buy)
+            .bind(&filter.min_nad_amount)
+            .bind(filter.cursor.map(|cursor| cursor.key))
+            .bind(filter.cursor.map(|cursor| cursor.id))
+            .bind(filter.limit)
+            .fetch_all(&self.db.pool)
+            .await?;
+
+        let next_cursor = if rows.len() as i64 == filter.limit {
+            rows.last().map(|row| {
+                Cursor {
+                    key: row.swap.created_at,
+                    id: row.swap.id,
+                }
+                .to_string()
+            })
+        } else {
+            None
+        };
+
+        let trades = rows
+            .into_iter()
+            .map(|row| Trade {
+                swap: row.swap,
+                user_info: UserInfo {
+                    nickname: row.user_nickname,
+                    image_uri: row.user_image_uri,
+                },
+            })
+            .collect();
+
+        Ok(TradesResponse {
+            trades,
+            next_cursor,
+        })
+    }
+}
@@ -4,8 +4,10 @@ use crate::types::{
         NewSwapMessage, NewTokenMessage,
     },
     model::Token,
+    siwe::NonceStore,
 };
 use anyhow::{Context, Result};
+use axum::async_trait;
 use axum_extra::handler::Or;
 use chrono::Utc;
 use futures::future::try_join_all;
@@ -24,7 +26,11 @@ lazy_static! {
     static ref NEW_TOKEN_KEY: &'static str = "new_token";
     static ref NEW_BUY_KEY: &'static str = "new_buy";
     static ref NEW_SELL_KEY: &'static str = "new_sell";
+    static ref SIWE_NONCE_KEY: &'static str = "siwe_nonce";
 }
+
+/// Seconds a sign-in nonce stays valid
+pub const NONCE_TTL: u64 = 300;
 pub struct RedisDatabase {
     pub client: Client,
 }
@@ -205,10 +211,6 @@ impl RedisDatabase {
         // 기존 데이터 삭제
         pipe.del(key);
 
-        if tokens.is_empty() {
-            return Ok(());
-        }
-
         // 코인 데이터를 JSON으로 직렬화하고 정렬된 집합에 추가
         for token_with_score in tokens {
             let token_json = serde_json::to_string(&token_with_score.token)
@@ -328,4 +330,21 @@ impl RedisDatabase {
             None => Ok(None),
         }
     }
+
+    pub async fn set_nonce(&self, nonce: &str) -> Result<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let key = format!("{}:{}", *SIWE_NONCE_KEY, nonce);
+        conn.set_ex::<_, _, ()>(key, 1, NONCE_TTL).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NonceStore for RedisDatabase {
+    async fn take_nonce(&self, nonce: &str) -> Result<bool> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+        let key = format!("{}:{}", *SIWE_NONCE_KEY, nonce);
+        let removed: i32 = conn.del(key).await?;
+        Ok(removed == 1)
+    }
 }
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use crate::{
+    constant::change_channels::{CURVE, TOKEN},
+    db::postgres::{controller::achievement::AchievementController, PostgresDatabase},
+    types::{
+        achievement::ACHIEVEMENT_RULES,
+        event::capture::AchievementCapture,
+        model::{Curve, FromValue, Token},
+    },
+};
+use anyhow::{Context, Result};
+
+use futures::StreamExt;
+
+use serde_json::Value;
+use sqlx::postgres::{PgListener, PgNotification};
+use tracing::{error, info, instrument, warn};
+
+#[instrument(skip(evaluator))]
+pub async fn main(evaluator: Arc<AchievementEvaluator>) -> Result<()> {
+    info!("Starting achievement event capture");
+
+    loop {
+        if let Err(e) = evaluator.change_data_capture().await {
+            error!("Error in achievement change_data_capture: {:?}", e);
+            info!("Retrying in 5 seconds...");
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        } else {
+            warn!("Achievement event capture completed unexpectedly");
+            break;
+        }
+    }
+    error!("Achievement event capture ended");
+    Ok(())
+}
+
+#[derive(Clone)]
+pub struct AchievementEvaluator {
+    db: Arc<PostgresDatabase>,
+}
+
+impl AchievementEvaluator {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        Self { db }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn change_data_capture(&self) -> Result<()> {
+        let mut channels: Vec<&str> = ACHIEVEMENT_RULES
+            .iter()
+            .map(|definition| definition.rule.channel())
+            .collect();
+        channels.sort_unstable();
+        channels.dedup();
+
+        let mut listener = PgListener::connect_with(&self.db.pool).await?;
+        listener.listen_all(channels).await?;
+        let mut stream = listener.into_stream();
+        info!("Achievement event capture started");
+
+        while let Some(notification) = stream.next().await {
+            let evaluator = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = evaluator.handle_notification(notification).await {
+                    error!("Error handling notification: {:?}", e);
+                }
+            });
+        }
+
+        error!("Achievement changing data capture ended");
+        Ok(())
+    }
+
+    async fn handle_notification(
+        &self,
+        notification: Result<PgNotification, sqlx::Error>,
+    ) -> Result<()> {
+        let notification = notification.context("Failed to get notification")?;
+
+        let payload: Value = serde_json::from_str(notification.payload())
+            .context("Failed to parse notification payload")?;
+
+        let event = match notification.channel() {
+            TOKEN => AchievementCapture::Token(Token::from_value(payload)?),
+            CURVE => AchievementCapture::Curve(Curve::from_value(payload)?),
+            _ => return Ok(()),
+        };
+
+        self.evaluate(&event).await
+    }
+
+    async fn evaluate(&self, event: &AchievementCapture) -> Result<()> {
+        let achievement_controller = AchievementController::new(self.db.clone());
+        let token_id = event.token_id();
+
+        for definition in ACHIEVEMENT_RULES.iter() {
+            if !definition.rule.matches(event) {
+                continue;
+            }
+            let awarded = achievement_controller
+                .evaluate(definition, Some(token_id))
+                .await?;
+            if awarded > 0 {
+                info!(
+                    "Awarded {} to {} account(s) on token {}",
+                    definition.name, awarded, token_id
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
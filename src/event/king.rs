@@ -0,0 +1,154 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use crate::{
+    constant::change_channels::KING,
+    db::postgres::{controller::king::KingController, PostgresDatabase},
+    types::{
+        event::king::KingMessage,
+        model::{FromValue, King},
+    },
+};
+use anyhow::{Context, Result};
+
+use futures::StreamExt;
+
+use serde_json::Value;
+use sqlx::postgres::{PgListener, PgNotification};
+use tokio::sync::broadcast::{self, Receiver, Sender};
+use tracing::{debug, error, info, instrument, warn};
+
+#[instrument(skip(producer))]
+pub async fn main(producer: Arc<KingEventProducer>) -> Result<()> {
+    info!("Starting king event capture");
+
+    loop {
+        if let Err(e) = producer.change_data_capture().await {
+            error!("Error in king change_data_capture: {:?}", e);
+            info!("Retrying in 5 seconds...");
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        } else {
+            warn!("King event capture completed unexpectedly");
+            break;
+        }
+    }
+    error!("King event capture ended");
+    Ok(())
+}
+
+pub struct KingReceiver {
+    receiver: Receiver<KingMessage>,
+    controller: Arc<KingEventProducer>,
+}
+
+impl KingReceiver {
+    pub async fn recv(&mut self) -> Option<KingMessage> {
+        self.receiver.recv().await.ok()
+    }
+}
+
+impl Drop for KingReceiver {
+    fn drop(&mut self) {
+        let controller = self.controller.clone();
+        tokio::spawn(async move {
+            controller.decrement_receiver_count().await;
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct KingEventProducer {
+    db: Arc<PostgresDatabase>,
+    king_sender: Arc<Sender<KingMessage>>,
+    total_channels: Arc<AtomicUsize>,
+}
+
+impl KingEventProducer {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        let (sender, _) = broadcast::channel(100);
+        Self {
+            db,
+            king_sender: Arc::new(sender),
+            total_channels: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn change_data_capture(&self) -> Result<()> {
+        let mut listener = PgListener::connect_with(&self.db.pool).await?;
+        listener.listen(KING).await?;
+        let mut stream = listener.into_stream();
+        info!("King event capture started");
+
+        while let Some(notification) = stream.next().await {
+            let producer = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = producer.handle_notification(notification).await {
+                    error!("Error handling notification: {:?}", e);
+                }
+            });
+        }
+
+        error!("King changing data capture ended");
+        Ok(())
+    }
+
+    async fn handle_notification(
+        &self,
+        notification: Result<PgNotification, sqlx::Error>,
+    ) -> Result<()> {
+        let notification = notification.context("Failed to get notification")?;
+
+        let payload: Value = serde_json::from_str(notification.payload())
+            .context("Failed to parse notification payload")?;
+        let king = King::from_value(payload)?;
+
+        let king_controller = KingController::new(self.db.clone());
+        let king = king_controller.get_king_token(king).await?;
+        info!("New king: {:?}", king.token.id);
+
+        self.send_king_message(KingMessage { king: Some(king) })
+            .await
+    }
+
+    async fn send_king_message(&self, king_message: KingMessage) -> Result<()> {
+        let receiver_count = self.total_channels.load(Ordering::Relaxed);
+        if receiver_count > 0 {
+            match self.king_sender.send(king_message) {
+                Ok(_) => {
+                    debug!("Sent king message to {} receivers", receiver_count);
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("Failed to send king message: {:?}", e);
+                    Err(anyhow::anyhow!("Failed to send king message"))
+                }
+            }
+        } else {
+            debug!("No receivers to send king message to");
+            Ok(())
+        }
+    }
+
+    pub async fn get_king_receiver(&self) -> KingReceiver {
+        self.total_channels.fetch_add(1, Ordering::SeqCst);
+        info!(
+            "King Receiver count: {}",
+            self.total_channels.load(Ordering::SeqCst)
+        );
+        KingReceiver {
+            receiver: self.king_sender.subscribe(),
+            controller: Arc::new(self.clone()),
+        }
+    }
+
+    async fn decrement_receiver_count(&self) {
+        self.total_channels.fetch_sub(1, Ordering::SeqCst);
+        info!(
+            "King Receiver count: {}",
+            self.total_channels.load(Ordering::SeqCst)
+        );
+    }
+}
@@ -1,4 +1,7 @@
+pub mod achievement;
+pub mod king;
 pub mod token;
 
 pub mod new_content;
 pub mod order;
+pub mod search;
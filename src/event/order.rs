@@ -19,12 +19,12 @@ use crate::{
     types::{
         event::{
             capture::OrderEventCapture,
-            order::{OrderMessage, OrderTokenResponse, OrderType},
+            order::{latest_reply_score, OrderMessage, OrderTokenResponse, OrderType},
             NewSwapMessage, NewTokenMessage, SendMessageType,
         },
         model::{
-            Curve, CurveWrapper, FromValue, Swap, SwapWrapper, Token, TokenReplyCount,
-            TokenReplyCountWrapper, TokenWrapper,
+            Curve, CurveWrapper, FromValue, Swap, SwapWrapper, Thread, ThreadWrapper, Token,
+            TokenReplyCount, TokenReplyCountWrapper, TokenWrapper,
         },
     },
 };
@@ -169,7 +169,7 @@ impl OrderEventProducer {
     pub async fn change_data_capture(&self) -> Result<()> {
         let mut listener = PgListener::connect_with(&self.db.pool).await?;
         listener
-            .listen_all(vec![TOKEN, SWAP, CURVE, TOKEN_REPLIES_COUNT])
+            .listen_all(vec![TOKEN, SWAP, CURVE, TOKEN_REPLIES_COUNT, THREAD])
             .await?;
         let mut stream = listener.into_stream();
         info!("Order event capture Start");
@@ -208,6 +208,9 @@ impl OrderEventProducer {
             TOKEN_REPLIES_COUNT => Ok(OrderEventCapture::ReplyChange(TokenReplyCount::from_value(
                 payload,
             )?)),
+            THREAD => Ok(OrderEventCapture::ThreadChange(ThreadWrapper::from_value(
+                payload,
+            )?)),
 
             _ => Err(anyhow::anyhow!("Unknown channel: {}", channel)),
         }
@@ -247,6 +250,9 @@ impl OrderEventProducer {
             OrderEventCapture::ReplyChange(token_reply) => {
                 self.handle_reply_change_order(token_reply).await
             }
+            OrderEventCapture::ThreadChange(thread) => {
+                self.handle_thread_change_order(thread).await
+            }
         }
     }
 
@@ -297,7 +303,7 @@ impl OrderEventProducer {
         let order_repsonse = order_controller
             .get_order_token_response_by_token(&curve.token_id)
             .await?;
-        let score = curve.price.to_string();
+        let score = curve.market_cap().to_string();
         let result = self
             .redis
             .add_to_market_cap_order(&order_repsonse, score)
@@ -308,13 +314,13 @@ impl OrderEventProducer {
     async fn add_token_last_reply_order(
         &self,
         db: Arc<PostgresDatabase>,
-        token_reply: TokenReplyCount,
+        thread: Thread,
     ) -> Result<Option<OrderTokenResponse>> {
         let order_controller = OrderController::new(db);
         let order_repsonse = order_controller
-            .get_order_token_response_by_token(&token_reply.token_id)
+            .get_order_token_response_by_token(&thread.token_id)
             .await?;
-        let score = order_repsonse.created_at.to_string();
+        let score = latest_reply_score(&thread.created_at);
         let redis_result = self
             .redis
             .add_to_last_reply_order(&order_repsonse, score.to_string())
@@ -400,25 +406,11 @@ impl OrderEventProducer {
         token_reply: TokenReplyCount,
     ) -> Result<Vec<OrderMessage>> {
         info!("Handle_reply_change_order start");
-        let lastest_reply_result = self
-            .add_token_last_reply_order(self.db.clone(), token_reply.clone())
-            .await?;
         let reply_count_result = self
             .add_token_reply_count_order(self.db.clone(), token_reply.clone())
             .await?;
         let mut messages = Vec::new();
 
-        if let Some(last_reply_token) = lastest_reply_result {
-            messages.push(OrderMessage {
-                // message_type: SendMessageType::Regular,
-                // new_token: None,
-                // new_buy: None,
-                // new_sell: None,
-                order_type: OrderType::LatestReply,
-                order_token: Some(vec![last_reply_token]),
-            });
-        }
-
         if let Some(reply_count_token) = reply_count_result {
             messages.push(OrderMessage {
                 // message_type: SendMessageType::Regular,
@@ -433,6 +425,47 @@ impl OrderEventProducer {
         Ok(messages)
     }
 
+    async fn handle_thread_change_order(&self, thread: ThreadWrapper) -> Result<Vec<OrderMessage>> {
+        match thread.operation.as_str() {
+            "INSERT" => {
+                match self
+                    .add_token_last_reply_order(self.db.clone(), thread.record)
+                    .await?
+                {
+                    Some(token) => Ok(vec![OrderMessage {
+                        order_type: OrderType::LatestReply,
+                        order_token: Some(vec![token]),
+                    }]),
+                    None => Ok(vec![]),
+                }
+            }
+            "DELETE" => self.handle_thread_delete_order(thread.record).await,
+            // Edits keep created_at
+            _ => Ok(vec![]),
+        }
+    }
+
+    async fn handle_thread_delete_order(&self, thread: Thread) -> Result<Vec<OrderMessage>> {
+        let order_controller = OrderController::new(self.db.clone());
+        let latest = order_controller
+            .get_latest_reply_at(&thread.token_id)
+            .await?;
+        // Only deleting the token's newest thread changes its score
+        if latest.is_some_and(|latest| latest >= thread.created_at) {
+            return Ok(vec![]);
+        }
+
+        // Rebuilt like at startup, so a token that drops out makes room for the next one
+        let tokens = order_controller.get_latest_reply_order_token().await?;
+        self.redis.set_last_reply_order(tokens).await?;
+        let order = self.redis.get_last_reply_order().await?;
+
+        Ok(vec![OrderMessage {
+            order_type: OrderType::LatestReply,
+            order_token: Some(order),
+        }])
+    }
+
     pub async fn get_order_receiver(&self, order_type: OrderType) -> OrderReceiver {
         let mut senders = self.order_senders.write().await;
         let (sender, count) = senders.entry(order_type).or_insert_with(|| {
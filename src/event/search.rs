@@ -0,0 +1,351 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use crate::{
+    constant::change_channels::{CURVE, TOKEN},
+    db::postgres::{
+        controller::order::{market_cap_sql, OrderController},
+        PostgresDatabase,
+    },
+    types::{
+        event::order::OrderTokenResponse,
+        model::{Curve, FromValue, Token},
+    },
+};
+use anyhow::{Context, Result};
+use bigdecimal::BigDecimal;
+use futures::StreamExt;
+use serde_json::Value;
+use sqlx::postgres::{PgListener, PgNotification};
+use tokio::{sync::RwLock, time::sleep};
+use tracing::{error, info, instrument, warn};
+
+#[instrument(skip(index))]
+pub async fn main(index: Arc<TokenSearchIndex>) -> Result<()> {
+    info!("Starting token search index");
+
+    loop {
+        match index.change_data_capture().await {
+            Ok(_) => {
+                warn!("Token search index capture completed unexpectedly");
+                break;
+            }
+            Err(e) => {
+                error!("Error in token search index change_data_capture: {:?}", e);
+                info!("Retrying in 5 seconds...");
+                sleep(Duration::from_secs(5)).await;
+            }
+        }
+    }
+
+    error!("Token search index capture ended");
+    Ok(())
+}
+
+struct IndexedToken {
+    name: String,
+    symbol: String,
+    keys: Vec<String>,
+    market_cap: BigDecimal,
+}
+
+impl IndexedToken {
+    fn new(name: &str, symbol: &str, market_cap: BigDecimal) -> Self {
+        let name = name.to_lowercase();
+        let symbol = symbol.to_lowercase();
+        let mut keys: Vec<String> = name.split_whitespace().map(str::to_string).collect();
+        keys.push(name.clone());
+        keys.push(symbol.clone());
+        keys.sort_unstable();
+        keys.dedup();
+        IndexedToken {
+            name,
+            symbol,
+            keys,
+            market_cap,
+        }
+    }
+
+    /// Lower is better: exact match, symbol prefix, name prefix, then name word prefix
+    fn rank(&self, prefix: &str) -> u8 {
+        if self.symbol == prefix || self.name == prefix {
+            0
+        } else if self.symbol.starts_with(prefix) {
+            1
+        } else if self.name.starts_with(prefix) {
+            2
+        } else {
+            3
+        }
+    }
+}
+
+#[derive(Default)]
+struct IndexState {
+    /// Lowercase name, symbol and name words -> token ids
+    keys: BTreeMap<String, BTreeSet<String>>,
+    tokens: HashMap<String, IndexedToken>,
+}
+
+impl IndexState {
+    /// Indexes the token's name and symbol, keeping its known market cap
+    fn upsert(&mut self, id: &str, name: &str, symbol: &str) {
+        let market_cap = self
+            .remove(id)
+            .map(|token| token.market_cap)
+            .unwrap_or_default();
+        self.insert(id, name, symbol, market_cap);
+    }
+
+    fn insert(&mut self, id: &str, name: &str, symbol: &str, market_cap: BigDecimal) {
+        let token = IndexedToken::new(name, symbol, market_cap);
+        for key in &token.keys {
+            self.keys
+                .entry(key.clone())
+                .or_default()
+                .insert(id.to_string());
+        }
+        self.tokens.insert(id.to_string(), token);
+    }
+
+    fn set_market_cap(&mut self, id: &str, market_cap: BigDecimal) {
+        if let Some(token) = self.tokens.get_mut(id) {
+            token.market_cap = market_cap;
+        }
+    }
+
+    fn remove(&mut self, id: &str) -> Option<IndexedToken> {
+        let token = self.tokens.remove(id)?;
+        for key in &token.keys {
+            if let Some(ids) = self.keys.get_mut(key) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    self.keys.remove(key);
+                }
+            }
+        }
+        Some(token)
+    }
+
+    /// Ids of the best `limit` tokens whose name, symbol or a name word starts with
+    /// `prefix`, ranked by match quality then market cap over every match
+    fn candidates(&self, prefix: &str, limit: usize) -> Vec<String> {
+        let ids: BTreeSet<&String> = self
+            .keys
+            .range(prefix.to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .flat_map(|(_, ids)| ids)
+            .collect();
+
+        let mut candidates: Vec<(u8, Reverse<&BigDecimal>, &String)> = ids
+            .into_iter()
+            .filter_map(|id| {
+                let token = self.tokens.get(id)?;
+                Some((token.rank(prefix), Reverse(&token.market_cap), id))
+            })
+            .collect();
+        if candidates.len() > limit {
+            candidates.select_nth_unstable(limit);
+            candidates.truncate(limit);
+        }
+        candidates.sort_unstable();
+
+        candidates
+            .into_iter()
+            .map(|(_, _, id)| id.clone())
+            .collect()
+    }
+}
+
+/// In-memory prefix index of token names and symbols for search-as-you-type,
+/// loaded at startup and kept current by the `new_token` and `update_curve` channels
+pub struct TokenSearchIndex {
+    db: Arc<PostgresDatabase>,
+    state: RwLock<IndexState>,
+}
+
+impl TokenSearchIndex {
+    pub fn new(db: Arc<PostgresDatabase>) -> Self {
+        Self {
+            db,
+            state: RwLock::new(IndexState::default()),
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn change_data_capture(&self) -> Result<()> {
+        let mut listener = PgListener::connect_with(&self.db.pool)
+            .await
+            .context("Failed to connect to database")?;
+        listener
+            .listen_all(vec![TOKEN, CURVE])
+            .await
+            .context("Failed to listen to channels")?;
+        // Listen first so nothing committed during the load is missed
+        self.load().await?;
+
+        let mut stream = listener.into_stream();
+        info!("Token search index capture started");
+
+        while let Some(notification) = stream.next().await {
+            if let Err(e) = self.handle_notification(notification).await {
+                error!("Error handling notification: {:?}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn load(&self) -> Result<()> {
+        let tokens: Vec<(String, String, String, BigDecimal)> = sqlx::query_as(&format!(
+            r#"
+            SELECT t.id, t.name, t.symbol, COALESCE({market_cap}, 0)
+            FROM token t
+            LEFT JOIN curve cu ON t.id = cu.token_id
+            "#,
+            market_cap = market_cap_sql("cu")
+        ))
+        .fetch_all(&self.db.pool)
+        .await
+        .context("Failed to load tokens")?;
+
+        let mut state = IndexState::default();
+        for (id, name, symbol, market_cap) in &tokens {
+            state.insert(id, name, symbol, market_cap.clone());
+        }
+        *self.state.write().await = state;
+        info!("Token search index loaded {} tokens", tokens.len());
+        Ok(())
+    }
+
+    async fn handle_notification(
+        &self,
+        notification: Result<PgNotification, sqlx::Error>,
+    ) -> Result<()> {
+        let notification = notification.context("Failed to get notification")?;
+        let payload: Value = serde_json::from_str(notification.payload())
+            .context("Failed to parse notification payload")?;
+        match notification.channel() {
+            TOKEN => {
+                let token = Token::from_value(payload)?;
+                self.state
+                    .write()
+                    .await
+                    .upsert(&token.id, &token.name, &token.symbol);
+            }
+            CURVE => {
+                let curve = Curve::from_value(payload)?;
+                self.state
+                    .write()
+                    .await
+                    .set_market_cap(&curve.token_id, curve.market_cap());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Suggestions for a partial query, ranked by match quality then market cap
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<OrderTokenResponse>> {
+        let prefix = query.trim().to_lowercase();
+        if prefix.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let ids = self.state.read().await.candidates(&prefix, limit);
+        let order_controller = OrderController::new(self.db.clone());
+        let mut tokens = order_controller.get_order_token_responses(&ids).await?;
+
+        // Tokens deleted upstream send no notification, so drop them once they stop resolving
+        if tokens.len() < ids.len() {
+            let mut state = self.state.write().await;
+            for id in ids
+                .iter()
+                .filter(|id| !tokens.iter().any(|token| token.id == **id))
+            {
+                state.remove(id);
+            }
+        }
+        tokens.sort_by_key(|token| ids.iter().position(|id| *id == token.id));
+
+        Ok(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(tokens: &[(&str, &str, &str, i64)]) -> IndexState {
+        let mut state = IndexState::default();
+        for (id, name, symbol, market_cap) in tokens {
+            state.insert(id, name, symbol, BigDecimal::from(*market_cap));
+        }
+        state
+    }
+
+    #[test]
+    fn ranks_match_quality_before_market_cap() {
+        let state = state(&[
+            ("0x1", "Moon Cat", "MCAT", 300),
+            ("0x2", "Moon", "MOON", 100),
+            ("0x3", "Blue Moon", "BLUE", 500),
+            ("0x4", "Mooncake", "CAKE", 200),
+        ]);
+
+        assert_eq!(
+            state.candidates("moon", 10),
+            vec!["0x2", "0x1", "0x4", "0x3"]
+        );
+    }
+
+    #[test]
+    fn keeps_highest_market_caps_among_many_matches() {
+        let tokens: Vec<(String, String, String, i64)> = (0..500)
+            .map(|i| {
+                (
+                    format!("0x{:03}", i),
+                    format!("Pepe {}", i),
+                    format!("PEPE{}", i),
+                    i,
+                )
+            })
+            .collect();
+        let mut state = IndexState::default();
+        for (id, name, symbol, market_cap) in &tokens {
+            state.insert(id, name, symbol, BigDecimal::from(*market_cap));
+        }
+
+        assert_eq!(state.candidates("pepe", 3), vec!["0x499", "0x498", "0x497"]);
+    }
+
+    #[test]
+    fn upsert_keeps_market_cap_and_reindexes() {
+        let mut state = state(&[
+            ("0x1", "Moon Cat", "MCAT", 300),
+            ("0x2", "Moon", "MOON", 100),
+        ]);
+
+        state.upsert("0x1", "Sun Cat", "SCAT");
+        assert_eq!(state.candidates("moon", 10), vec!["0x2"]);
+        assert_eq!(state.candidates("cat", 10), vec!["0x1"]);
+        assert_eq!(state.tokens["0x1"].market_cap, BigDecimal::from(300));
+
+        state.set_market_cap("0x2", BigDecimal::from(400));
+        state.upsert("0x2", "Sun", "SUN");
+        assert_eq!(state.candidates("sun", 10), vec!["0x2", "0x1"]);
+    }
+
+    #[test]
+    fn remove_drops_all_keys() {
+        let mut state = state(&[("0x1", "Moon Cat", "MCAT", 300)]);
+
+        assert!(state.remove("0x1").is_some());
+        assert!(state.candidates("m", 10).is_empty());
+        assert!(state.keys.is_empty());
+    }
+}
@@ -199,6 +199,7 @@ impl TokenEventProducer {
         Ok(TokenMessage::from_curve(curve))
     }
 
+    /// Forwarded as is; `operation` tells clients to add, update or remove the thread
     async fn handle_thread_event(&self, thread: ThreadWrapper) -> Result<TokenMessage> {
         Ok(TokenMessage::from_thread(thread))
     }
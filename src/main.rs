@@ -5,8 +5,11 @@ use anyhow::Result;
 use api_server::{
     db::{postgres::PostgresDatabase, redis::RedisDatabase},
     event::{
+        achievement::{self, AchievementEvaluator},
+        king::{self, KingEventProducer},
         new_content::{self, NewContentEventProducer},
         order::{self, OrderEventProducer},
+        search::{self, TokenSearchIndex},
         token::{self, TokenEventProducer},
     },
     server,
@@ -29,12 +32,18 @@ async fn main() -> Result<()> {
         postgres.clone(),
         redis.clone(),
     ));
+    let king_event_producer = Arc::new(KingEventProducer::new(postgres.clone()));
+    let achievement_evaluator = Arc::new(AchievementEvaluator::new(postgres.clone()));
+    let search_index = Arc::new(TokenSearchIndex::new(postgres.clone()));
     set.spawn(order::main(order_event_porducer.clone()));
     set.spawn(token::main(coin_event_producer.clone()));
     set.spawn(new_content::main(
         new_content_producer.clone(),
         redis.clone(),
     ));
+    set.spawn(king::main(king_event_producer.clone()));
+    set.spawn(achievement::main(achievement_evaluator));
+    set.spawn(search::main(search_index.clone()));
 
     set.spawn(server::main(
         postgres.clone(),
@@ -42,6 +51,8 @@ async fn main() -> Result<()> {
         order_event_porducer.clone(),
         coin_event_producer.clone(),
         new_content_producer.clone(),
+        king_event_producer.clone(),
+        search_index.clone(),
     ));
     while let Some(res) = set.join_next().await {
         match res {
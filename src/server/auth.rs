@@ -0,0 +1,74 @@
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts, RequestPartsExt};
+use axum_extra::{
+    headers::{authorization::Bearer, Authorization, Cookie},
+    TypedHeader,
+};
+
+use crate::db::postgres::controller::session::SessionController;
+
+use super::{result::AppError, state::AppState};
+
+pub const SESSION_COOKIE: &str = "session_id";
+/// Sessions don't expire server side; this only bounds the browser cookie
+const SESSION_COOKIE_MAX_AGE: u64 = 60 * 60 * 24 * 30;
+
+/// Account behind the request's session, taken from the `session_id` cookie or
+/// an `Authorization: Bearer <session_id>` header.
+///
+/// Use `Option<AuthSession>` for routes that also serve anonymous users.
+#[derive(Debug, Clone)]
+pub struct AuthSession {
+    pub session_id: String,
+    pub account_id: String,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthSession {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let session_id = session_id(parts)
+            .await
+            .ok_or_else(|| AppError::Unauthorized("Missing session".into()))?;
+
+        let session_controller = SessionController::new(state.postgres.clone());
+        let session = session_controller
+            .get_session(&session_id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid session".into()))?;
+
+        Ok(AuthSession {
+            session_id: session.id,
+            account_id: session.account_id,
+        })
+    }
+}
+
+async fn session_id(parts: &mut Parts) -> Option<String> {
+    if let Ok(TypedHeader(Authorization(bearer))) =
+        parts.extract::<TypedHeader<Authorization<Bearer>>>().await
+    {
+        return Some(bearer.token().to_string());
+    }
+
+    // Browsers can't set headers on a WebSocket upgrade, so the cookie is the fallback
+    let TypedHeader(cookie) = parts.extract::<TypedHeader<Cookie>>().await.ok()?;
+    cookie.get(SESSION_COOKIE).map(str::to_string)
+}
+
+pub fn session_cookie(session_id: &str) -> String {
+    format!(
+        "{}={}; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age={}",
+        SESSION_COOKIE, session_id, SESSION_COOKIE_MAX_AGE
+    )
+}
+
+pub fn expired_session_cookie() -> String {
+    format!(
+        "{}=; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age=0",
+        SESSION_COOKIE
+    )
+}
@@ -0,0 +1,67 @@
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Path, Query},
+    http::request::Parts,
+};
+use std::str::FromStr;
+
+use crate::{
+    db::postgres::controller::profile::ProfileController,
+    types::{
+        pagination::{Pagination, PaginationQuery},
+        profile::{is_address, Identifier},
+    },
+};
+
+use super::{result::AppError, state::AppState};
+
+#[async_trait]
+impl<S, C> FromRequestParts<S> for Pagination<C>
+where
+    S: Send + Sync,
+    C: FromStr,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<PaginationQuery>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| AppError::BadRequest(e.body_text()))?;
+        Pagination::try_from(query).map_err(AppError::BadRequest)
+    }
+}
+
+/// Account named by the route's `:user` segment, either a nickname or an address.
+///
+/// Rejects with `AppError::NotFound` when no such account exists.
+#[derive(Debug, Clone)]
+pub struct ResolvedAccount {
+    pub account_id: String,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for ResolvedAccount {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let Path(user) = Path::<String>::from_request_parts(parts, state)
+            .await
+            .map_err(|e| AppError::BadRequest(e.body_text()))?;
+        let identifier = if is_address(&user) {
+            Identifier::Address(user)
+        } else {
+            Identifier::Nickname(user)
+        };
+
+        let profile_controller = ProfileController::new(state.postgres.clone());
+        let account_id = profile_controller
+            .get_account_id(&identifier)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        Ok(ResolvedAccount { account_id })
+    }
+}
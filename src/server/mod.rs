@@ -1,3 +1,5 @@
+pub mod auth;
+pub mod extract;
 pub mod result;
 pub mod routes;
 pub mod state;
@@ -20,6 +22,8 @@ use axum::{
 
 
 use routes::{
+    achievement::{self, handler::AchievementHoldersResponse},
+    king::{self, handler::KingResponse},
     profile::{
         self,
         handler::{
@@ -27,10 +31,18 @@ use routes::{
             ProfileResponse, RepliesResponse,
         },
     },
-    search::{self, handler::SearchResponse},
-    socket
+    search::{
+        self,
+        handler::{SearchResponse, UnifiedSearchResponse},
+    },
+    socket, thread,
+    token::{
+        self,
+        handler::{ChartResponse, TokenDetailResponse},
+    },
 };
 
+use result::ErrorResponse;
 use state::AppState;
 use tower::ServiceBuilder;
 use tracing::info;
@@ -39,11 +51,24 @@ use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
     db::{postgres::PostgresDatabase, redis::RedisDatabase},
-    event::{new_content::NewContentEventProducer, order::OrderEventProducer, token::TokenEventProducer},
+    event::{
+        king::KingEventProducer, new_content::NewContentEventProducer, order::OrderEventProducer,
+        search::TokenSearchIndex, token::TokenEventProducer,
+    },
     types::{
-        event::{order::OrderTokenResponse, UserInfo},
-        model::{Account, Thread, Token},
-        profile::HoldToken,
+        event::{king::KingToken, order::OrderTokenResponse, UserInfo},
+        like::{LikedAccountsResponse, LikedThreadsResponse, LikersResponse},
+        siwe::{NonceResponse, VerifyRequest, VerifyResponse},
+        model::{Account, Achievement, Chart, Curve, Swap, Thread, Token},
+        portfolio::{PortfolioResponse, PortfolioToken},
+        search::{SearchSort, TransactionHit, TransactionKind},
+        profile::{CreatedTokenSort, FollowSort, HoldToken, HoldingSort, UpdateProfileRequest},
+        thread::{
+            CreateThreadRequest, RootThread, ThreadPost, ThreadRepliesResponse, ThreadSort,
+            ThreadsResponse, UpdateThreadRequest,
+        },
+        token::{HolderStats, HoldersResponse, TokenDetail, TokenHolder, UpdateTokenRequest},
+        trade::{Trade, TradeSide, TradesResponse},
     },
 };
 
@@ -51,12 +76,41 @@ use crate::{
 #[openapi(
     paths(
         search::handler::search_token,
+        search::handler::search,
         profile::handler::get_profile,
         profile::handler::get_tokens_held,
+        profile::handler::get_portfolio,
         profile::handler::get_replies,
         profile::handler::get_created_tokens,
         profile::handler::get_followers,
         profile::handler::get_following,
+        profile::handler::get_trades,
+        profile::handler::get_likes_received,
+        profile::handler::get_likes_given,
+        profile::handler::get_liked_accounts,
+        profile::handler::update_profile,
+        profile::handler::follow_account,
+        profile::handler::unfollow_account,
+        profile::handler::like_account,
+        profile::handler::unlike_account,
+        token::handler::get_token,
+        token::handler::get_charts,
+        token::handler::get_token_trades,
+        token::handler::get_token_threads,
+        token::handler::get_holders,
+        token::handler::update_token,
+        token::handler::create_thread,
+        thread::handler::update_thread,
+        thread::handler::delete_thread,
+        thread::handler::get_thread_replies,
+        thread::handler::get_thread_likers,
+        thread::handler::like_thread,
+        thread::handler::unlike_thread,
+        king::handler::get_kings,
+        achievement::handler::get_achievement_holders,
+        routes::auth::handler::get_nonce,
+        routes::auth::handler::verify,
+        routes::auth::handler::logout,
     ),
     components(
         schemas(
@@ -69,16 +123,62 @@ use crate::{
             Account,
             Token,
             HoldToken,
+            HoldingSort,
+            PortfolioResponse,
+            PortfolioToken,
+            CreatedTokenSort,
+            FollowSort,
             Thread,
             SearchResponse,
+            SearchSort,
+            UnifiedSearchResponse,
+            TransactionHit,
+            TransactionKind,
             OrderTokenResponse,
-            UserInfo
+            UserInfo,
+            TokenDetailResponse,
+            TokenDetail,
+            Curve,
+            ChartResponse,
+            Chart,
+            TradesResponse,
+            Trade,
+            TradeSide,
+            Swap,
+            ThreadsResponse,
+            ThreadRepliesResponse,
+            CreateThreadRequest,
+            UpdateThreadRequest,
+            UpdateProfileRequest,
+            UpdateTokenRequest,
+            LikersResponse,
+            LikedThreadsResponse,
+            LikedAccountsResponse,
+            RootThread,
+            ThreadPost,
+            ThreadSort,
+            HoldersResponse,
+            TokenHolder,
+            HolderStats,
+            KingResponse,
+            KingToken,
+            Achievement,
+            AchievementHoldersResponse,
+            NonceResponse,
+            VerifyRequest,
+            VerifyResponse,
+            ErrorResponse
             
         )
     ),
     tags(
         (name = "Search Token", description = "Search token by name"),
         (name = "Profile", description = "Get information about a user by Nickname"),
+        (name = "Token", description = "Get information about a token"),
+        (name = "Thread", description = "Manage threads, replies and likes"),
+        (name = "King", description = "Get king of the hill tokens"),
+        (name = "Achievement", description = "Get achievement holders"),
+        (name = "Auth", description = "Sign in with Ethereum"),
         
     )
 )]
@@ -89,7 +189,9 @@ pub async fn main(
     redis: Arc<RedisDatabase>,
     order_event_producer: Arc<OrderEventProducer>,
     token_event_producer: Arc<TokenEventProducer>,
-    new_content_producer:Arc<NewContentEventProducer>
+    new_content_producer:Arc<NewContentEventProducer>,
+    king_event_producer: Arc<KingEventProducer>,
+    search_index: Arc<TokenSearchIndex>,
 ) -> Result<()> {
     let ip = std::env::var("IP").unwrap();
     let port = std::env::var("PORT").unwrap();
@@ -98,13 +200,20 @@ pub async fn main(
         redis,
         order_event_producer,
         token_event_producer,
-        new_content_producer
+        new_content_producer,
+        king_event_producer,
+        search_index,
     };
     let app = Router::new()
         .route("/", get(|| async { "Hello, World!" }))
         .merge(socket::router())
         .merge(search::router())
         .merge(profile::router())
+        .merge(token::router())
+        .merge(thread::router())
+        .merge(king::router())
+        .merge(achievement::router())
+        .merge(routes::auth::router())
         // .merge(test::router())
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         
@@ -6,7 +6,10 @@ use axum::{
 };
 
 use redis::RedisError;
-use serde_json::json;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::types::siwe::SignInError;
 
 pub type AppResult<T> = Result<T, AppError>;
 pub type AppJsonResult<T> = AppResult<Json<T>>;
@@ -18,6 +21,8 @@ pub enum AppError {
     RedisError(String),
     Unauthorized(String),
     AuthError(String),
+    /// Authenticated, but not allowed to act on the resource
+    Forbidden(String),
     BadRequest(String),
     NotFound,
     InternalError(String),
@@ -30,14 +35,64 @@ impl From<RedisError> for AppError {
     }
 }
 
+impl From<SignInError> for AppError {
+    fn from(error: SignInError) -> Self {
+        match error {
+            SignInError::Malformed(error) => AppError::BadRequest(error.to_string()),
+            SignInError::Rejected(error) => AppError::AuthError(error.to_string()),
+            SignInError::Store(error) => AppError::AnyhowError(error),
+        }
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        match error {
+            sqlx::Error::RowNotFound => AppError::NotFound,
+            sqlx::Error::Database(ref err) if err.is_unique_violation() => AppError::Conflict,
+            error => AppError::AnyhowError(error.into()),
+        }
+    }
+}
+
 impl From<anyhow::Error> for AppError {
     fn from(error: Error) -> Self {
-        AppError::AnyhowError(error)
+        // Controllers return anyhow, so database errors are unwrapped here
+        match error.downcast::<sqlx::Error>() {
+            Ok(error) => error.into(),
+            Err(error) => AppError::AnyhowError(error),
+        }
+    }
+}
+
+/// Body of every error response
+#[derive(Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Machine-readable error code, e.g. `not_found`
+    pub code: &'static str,
+    /// Human-readable description
+    pub error: String,
+}
+
+impl AppError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::AnyhowError(_) | AppError::InternalError(_) => "internal_error",
+            AppError::RouteError(_) => "route_error",
+            AppError::RedisError(_) => "redis_error",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::AuthError(_) => "auth_error",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::BadRequest(_) => "bad_request",
+            AppError::NotFound => "not_found",
+            AppError::Conflict => "conflict",
+        }
     }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response<axum::body::Body> {
+        let code = self.code();
         let (status, error_message) = match self {
             AppError::RouteError(err) => (StatusCode::BAD_REQUEST, err),
             AppError::AnyhowError(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
@@ -47,6 +102,7 @@ impl IntoResponse for AppError {
             ),
             AppError::Conflict => (StatusCode::CONFLICT, "Conflict".into()),
             AppError::AuthError(msg) => (StatusCode::UNAUTHORIZED, msg),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
             AppError::NotFound => (StatusCode::NOT_FOUND, "Not Found".into()),
             AppError::InternalError(msg) => (
@@ -58,9 +114,10 @@ impl IntoResponse for AppError {
             }
         };
 
-        let body = Json(json!({
-            "error": error_message
-        }));
+        let body = Json(ErrorResponse {
+            code,
+            error: error_message,
+        });
 
         (status, body).into_response()
     }
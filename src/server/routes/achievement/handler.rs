@@ -0,0 +1,64 @@
+use crate::{
+    db::postgres::controller::achievement::AchievementController,
+    server::{
+        result::{AppError, AppJsonResult},
+        state::AppState,
+    },
+    types::{
+        model::{Account, Achievement},
+        pagination::{Pagination, PaginationQuery},
+    },
+};
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::Serialize;
+
+use utoipa::ToSchema;
+
+use super::path::AchievementPath;
+
+#[derive(ToSchema, Serialize)]
+pub struct AchievementHoldersResponse {
+    achievement: Achievement,
+    holders: Vec<Account>,
+    next_cursor: Option<String>,
+}
+
+/// Get accounts that earned an achievement
+#[utoipa::path(
+    get,
+    path = AchievementPath::Holders.docs_str(),
+    params(
+        ("name" = String, Path, description = "Achievement name, e.g. chad_creator"),
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "Achievement holders retrieved successfully", body = AchievementHoldersResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 404, description = "Achievement not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Achievement"
+)]
+pub async fn get_achievement_holders(
+    Path(name): Path<String>,
+    pagination: Pagination,
+    State(state): State<AppState>,
+) -> AppJsonResult<AchievementHoldersResponse> {
+    let achievement_controller = AchievementController::new(state.postgres.clone());
+    let achievement = achievement_controller
+        .get_achievement(&name)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let (holders, next_cursor) = achievement_controller
+        .get_holders(achievement.id, &pagination)
+        .await?;
+    Ok(Json(AchievementHoldersResponse {
+        achievement,
+        holders,
+        next_cursor,
+    }))
+}
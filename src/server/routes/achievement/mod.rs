@@ -0,0 +1,14 @@
+pub mod handler;
+pub mod path;
+use crate::server::state::AppState;
+
+use axum::{routing::get, Router};
+use handler::get_achievement_holders;
+use path::AchievementPath;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route(
+        AchievementPath::Holders.as_str(),
+        get(get_achievement_holders),
+    )
+}
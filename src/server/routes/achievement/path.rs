@@ -0,0 +1,18 @@
+#[derive(Debug)]
+pub enum AchievementPath {
+    Holders,
+}
+
+impl AchievementPath {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Holders => "/achievements/:name/holders",
+        }
+    }
+
+    pub fn docs_str(&self) -> &'static str {
+        match self {
+            Self::Holders => "/achievements/{name}/holders",
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    db::postgres::controller::session::SessionController,
+    server::{
+        auth::{expired_session_cookie, session_cookie, AuthSession},
+        result::{AppError, AppJsonResult, AppResult},
+        state::AppState,
+    },
+    types::siwe::{sign_in, NonceResponse, VerifyRequest, VerifyResponse},
+};
+
+use axum::{extract::State, http::header::SET_COOKIE, response::IntoResponse, Json};
+use chrono::Utc;
+use rand::{distributions::Alphanumeric, Rng};
+
+use super::path::AuthPath;
+
+fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// Get a sign-in nonce
+#[utoipa::path(
+    get,
+    path = AuthPath::Nonce.docs_str(),
+    responses(
+        (status = 200, description = "Nonce generated successfully", body = NonceResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn get_nonce(State(state): State<AppState>) -> AppJsonResult<NonceResponse> {
+    let nonce = random_string(16);
+    state.redis.set_nonce(&nonce).await?;
+    Ok(Json(NonceResponse { nonce }))
+}
+
+/// Sign in with a signed EIP-4361 message
+#[utoipa::path(
+    post,
+    path = AuthPath::Verify.docs_str(),
+    request_body = VerifyRequest,
+    responses(
+        (status = 200, description = "Signed in; the session is also set as the `session_id` cookie", body = VerifyResponse),
+        (status = 400, description = "Malformed message"),
+        (status = 401, description = "Invalid signature, nonce, domain or validity window"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn verify(
+    State(state): State<AppState>,
+    Json(request): Json<VerifyRequest>,
+) -> AppResult<impl IntoResponse> {
+    let domain = std::env::var("SIWE_DOMAIN")
+        .map_err(|_| AppError::InternalError("SIWE_DOMAIN is not set".into()))?;
+    let message = sign_in(state.redis.as_ref(), &request, &domain, Utc::now()).await?;
+
+    let session_id = random_string(32);
+    let session_controller = SessionController::new(state.postgres.clone());
+    let account_id = session_controller
+        .create_session(&message.address, &session_id)
+        .await?;
+
+    Ok((
+        [(SET_COOKIE, session_cookie(&session_id))],
+        Json(VerifyResponse {
+            account_id,
+            session_id,
+        }),
+    ))
+}
+
+/// Sign out of the current session
+#[utoipa::path(
+    post,
+    path = AuthPath::Logout.docs_str(),
+    responses(
+        (status = 204, description = "Signed out"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn logout(
+    session: AuthSession,
+    State(state): State<AppState>,
+) -> AppResult<impl IntoResponse> {
+    let session_controller = SessionController::new(state.postgres.clone());
+    session_controller
+        .delete_session(&session.session_id)
+        .await?;
+    Ok((
+        axum::http::StatusCode::NO_CONTENT,
+        [(SET_COOKIE, expired_session_cookie())],
+    ))
+}
@@ -0,0 +1,17 @@
+pub mod handler;
+pub mod path;
+use crate::server::state::AppState;
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+use handler::{get_nonce, logout, verify};
+use path::AuthPath;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(AuthPath::Nonce.as_str(), get(get_nonce))
+        .route(AuthPath::Verify.as_str(), post(verify))
+        .route(AuthPath::Logout.as_str(), post(logout))
+}
@@ -0,0 +1,20 @@
+#[derive(Debug)]
+pub enum AuthPath {
+    Nonce,
+    Verify,
+    Logout,
+}
+
+impl AuthPath {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Nonce => "/auth/nonce",
+            Self::Verify => "/auth/verify",
+            Self::Logout => "/auth/logout",
+        }
+    }
+
+    pub fn docs_str(&self) -> &'static str {
+        self.as_str()
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{
+    db::postgres::controller::king::KingController,
+    server::{result::AppJsonResult, state::AppState},
+    types::event::king::KingToken,
+};
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use utoipa::{IntoParams, ToSchema};
+
+use super::path::KingPath;
+
+const KING_LIMIT: i64 = 20;
+const MAX_KING_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct KingQuery {
+    /// Maximum number of historical kings to return
+    limit: Option<i64>,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct KingResponse {
+    current: Option<KingToken>,
+    history: Vec<KingToken>,
+}
+
+/// Get the current and past kings of the hill
+#[utoipa::path(
+    get,
+    path = KingPath::King.docs_str(),
+    params(KingQuery),
+    responses(
+        (status = 200, description = "Kings retrieved successfully", body = KingResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "King"
+)]
+pub async fn get_kings(
+    Query(query): Query<KingQuery>,
+    State(state): State<AppState>,
+) -> AppJsonResult<KingResponse> {
+    let limit = query.limit.unwrap_or(KING_LIMIT).clamp(1, MAX_KING_LIMIT);
+    let king_controller = KingController::new(state.postgres.clone());
+    let history = king_controller.get_kings(limit).await?;
+    Ok(Json(KingResponse {
+        current: history.first().cloned(),
+        history,
+    }))
+}
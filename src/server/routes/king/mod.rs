@@ -0,0 +1,11 @@
+pub mod handler;
+pub mod path;
+use crate::server::state::AppState;
+
+use axum::{routing::get, Router};
+use handler::get_kings;
+use path::KingPath;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route(KingPath::King.as_str(), get(get_kings))
+}
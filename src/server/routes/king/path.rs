@@ -0,0 +1,18 @@
+#[derive(Debug)]
+pub enum KingPath {
+    King,
+}
+
+impl KingPath {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::King => "/king",
+        }
+    }
+
+    pub fn docs_str(&self) -> &'static str {
+        match self {
+            Self::King => "/king",
+        }
+    }
+}
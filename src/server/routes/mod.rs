@@ -1,3 +1,8 @@
+pub mod achievement;
+pub mod auth;
+pub mod king;
 pub mod profile;
 pub mod search;
 pub mod socket;
+pub mod thread;
+pub mod token;
@@ -1,16 +1,33 @@
 use crate::{
-    db::postgres::controller::profile::ProfileController,
-    server::{result::AppJsonResult, state::AppState},
+    db::postgres::controller::{
+        follow::FollowController, like::LikeController, profile::ProfileController,
+        trade::TradeController,
+    },
+    server::{
+        auth::AuthSession,
+        extract::ResolvedAccount,
+        result::{AppError, AppJsonResult, AppResult},
+        state::AppState,
+    },
     types::{
-        model::{Account, Thread, Token},
-        profile::{HoldToken, Identifier},
+        like::{LikedAccountsResponse, LikedThreadsResponse, LikersResponse},
+        model::{Account, Achievement, Thread, Token},
+        pagination::{Cursor, Pagination, PaginationQuery},
+        portfolio::PortfolioResponse,
+        profile::{
+            CreatedTokenQuery, FollowQuery, HoldToken, HoldingQuery, ProfileReplyQuery,
+            UpdateProfileRequest,
+        },
+        trade::{TradeFilter, TradeQuery, TradesResponse},
     },
 };
 
 use axum::{
-    extract::{Path, State},
+    extract::{Query, State},
+    http::StatusCode,
     Json,
 };
+use bigdecimal::BigDecimal;
 use serde::Serialize;
 
 use utoipa::ToSchema;
@@ -20,33 +37,79 @@ use super::path::ProfilePath;
 #[derive(Serialize, utoipa::ToSchema)]
 pub struct ProfileResponse {
     account: Account,
+    achievements: Vec<Achievement>,
 }
 #[derive(ToSchema, Serialize)]
 pub struct HeldTokensResponse {
     tokens: Vec<HoldToken>,
+    next_cursor: Option<String>,
 }
 #[derive(ToSchema, Serialize)]
 pub struct RepliesResponse {
     replies: Vec<Thread>,
+    next_cursor: Option<String>,
 }
 #[derive(ToSchema, Serialize)]
 pub struct CreatedTokensResponse {
     tokens: Vec<Token>,
+    next_cursor: Option<String>,
 }
 
 #[derive(ToSchema, Serialize)]
 pub struct FollowersResponse {
     followers: Vec<Account>,
+    next_cursor: Option<String>,
 }
 
 #[derive(ToSchema, Serialize)]
 pub struct FollowingResponse {
     following: Vec<Account>,
+    next_cursor: Option<String>,
 }
 
-fn is_address(user: &str) -> bool {
-    // 주소 형식 검증 로직 (예: 0x로 시작하고 적절한 길이인지 확인)
-    user.starts_with("0x") && user.len() == 42
+/// Update the signed-in user's profile
+#[utoipa::path(
+    patch,
+    path = ProfilePath::Edit.docs_str(),
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated successfully", body = Account),
+        (status = 400, description = "Invalid nickname, bio or image uri"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 409, description = "Nickname already taken"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Profile"
+)]
+pub async fn update_profile(
+    session: AuthSession,
+    State(state): State<AppState>,
+    Json(request): Json<UpdateProfileRequest>,
+) -> AppJsonResult<Account> {
+    request.validate().map_err(AppError::BadRequest)?;
+    let profile_controller = ProfileController::new(state.postgres.clone());
+    if let Some(nickname) = &request.nickname {
+        if profile_controller
+            .is_nickname_taken(nickname, &session.account_id)
+            .await?
+        {
+            return Err(AppError::Conflict);
+        }
+    }
+    let account = profile_controller
+        .update_profile(&session.account_id, &request)
+        .await?;
+    Ok(Json(account))
+}
+
+/// Rejects social actions aimed at the caller's own account
+fn ensure_not_self(target: &ResolvedAccount, session: &AuthSession) -> AppResult<()> {
+    if target.account_id == session.account_id {
+        return Err(AppError::BadRequest(
+            "Cannot target your own account".into(),
+        ));
+    }
+    Ok(())
 }
 
 /// Get user profile
@@ -64,17 +127,18 @@ fn is_address(user: &str) -> bool {
     tag = "Profile"
 )]
 pub async fn get_profile(
-    Path(user): Path<String>,
+    user: ResolvedAccount,
     State(state): State<AppState>,
 ) -> AppJsonResult<ProfileResponse> {
     let profile_controller = ProfileController::new(state.postgres.clone());
-    let identifier = if is_address(&user) {
-        Identifier::Address(user)
-    } else {
-        Identifier::Nickname(user)
-    };
-    let account = profile_controller.get_profile(&identifier).await?;
-    Ok(Json(ProfileResponse { account }))
+    let account = profile_controller.get_profile(&user.account_id).await?;
+    let achievements = profile_controller
+        .get_achievements(&user.account_id)
+        .await?;
+    Ok(Json(ProfileResponse {
+        account,
+        achievements,
+    }))
 }
 
 /// Get user's held tokens
@@ -82,27 +146,61 @@ pub async fn get_profile(
     get,
     path = ProfilePath::TokenHeld.docs_str(),
     params(
-        ("user" = String, Path, description = "User's nickname or Ethereum address")
+        ("user" = String, Path, description = "User's nickname or Ethereum address"),
+        HoldingQuery,
+        PaginationQuery
     ),
     responses(
         (status = 200, description = "User's held tokens retrieved successfully", body = HeldTokensResponse),
+        (status = 400, description = "Invalid query"),
         (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Profile"
 )]
 pub async fn get_tokens_held(
-    Path(user): Path<String>,
+    account: ResolvedAccount,
+    Query(query): Query<HoldingQuery>,
+    pagination: Pagination<Cursor<BigDecimal>>,
     State(state): State<AppState>,
 ) -> AppJsonResult<HeldTokensResponse> {
     let profile_controller = ProfileController::new(state.postgres.clone());
-    let identifier = if is_address(&user) {
-        Identifier::Address(user)
-    } else {
-        Identifier::Nickname(user)
-    };
-    let tokens = profile_controller.get_holding_token(&identifier).await?;
-    Ok(Json(HeldTokensResponse { tokens }))
+    let (tokens, next_cursor) = profile_controller
+        .get_holding_token(
+            &account.account_id,
+            query.sort.unwrap_or_default(),
+            &pagination,
+        )
+        .await?;
+    Ok(Json(HeldTokensResponse {
+        tokens,
+        next_cursor,
+    }))
+}
+
+/// Get user's portfolio value and PnL
+#[utoipa::path(
+    get,
+    path = ProfilePath::Portfolio.docs_str(),
+    params(
+        ("user" = String, Path, description = "User's nickname or Ethereum address")
+    ),
+    responses(
+        (status = 200, description = "User's portfolio retrieved successfully", body = PortfolioResponse),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Profile"
+)]
+pub async fn get_portfolio(
+    account: ResolvedAccount,
+    State(state): State<AppState>,
+) -> AppJsonResult<PortfolioResponse> {
+    let profile_controller = ProfileController::new(state.postgres.clone());
+    let portfolio = profile_controller
+        .get_portfolio(&account.account_id)
+        .await?;
+    Ok(Json(portfolio))
 }
 
 /// Get user's replies
@@ -110,27 +208,36 @@ pub async fn get_tokens_held(
     get,
     path = ProfilePath::Replies.docs_str(),
     params(
-        ("user" = String, Path, description = "User's nickname or Ethereum address")
+        ("user" = String, Path, description = "User's nickname or Ethereum address"),
+        ProfileReplyQuery,
+        PaginationQuery
     ),
     responses(
         (status = 200, description = "User's replies retrieved successfully", body = RepliesResponse),
+        (status = 400, description = "Invalid query"),
         (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Profile"
 )]
 pub async fn get_replies(
-    Path(user): Path<String>,
+    account: ResolvedAccount,
+    Query(query): Query<ProfileReplyQuery>,
+    pagination: Pagination,
     State(state): State<AppState>,
 ) -> AppJsonResult<RepliesResponse> {
     let profile_controller = ProfileController::new(state.postgres.clone());
-    let identifier = if is_address(&user) {
-        Identifier::Address(user)
-    } else {
-        Identifier::Nickname(user)
-    };
-    let replies = profile_controller.get_replies(&identifier).await?;
-    Ok(Json(RepliesResponse { replies }))
+    let (replies, next_cursor) = profile_controller
+        .get_replies(
+            &account.account_id,
+            query.sort.unwrap_or_default(),
+            &pagination,
+        )
+        .await?;
+    Ok(Json(RepliesResponse {
+        replies,
+        next_cursor,
+    }))
 }
 
 /// Get tokens created by user
@@ -138,27 +245,36 @@ pub async fn get_replies(
     get,
     path = ProfilePath::TokenCreated.docs_str(),
     params(
-        ("user" = String, Path, description = "User's nickname or Ethereum address")
+        ("user" = String, Path, description = "User's nickname or Ethereum address"),
+        CreatedTokenQuery,
+        PaginationQuery
     ),
     responses(
         (status = 200, description = "Created tokens retrieved successfully", body = CreatedTokensResponse),
+        (status = 400, description = "Invalid query"),
         (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Profile"
 )]
 pub async fn get_created_tokens(
-    Path(user): Path<String>,
+    account: ResolvedAccount,
+    Query(query): Query<CreatedTokenQuery>,
+    pagination: Pagination<Cursor<BigDecimal, String>>,
     State(state): State<AppState>,
 ) -> AppJsonResult<CreatedTokensResponse> {
     let profile_controller = ProfileController::new(state.postgres.clone());
-    let identifier = if is_address(&user) {
-        Identifier::Address(user)
-    } else {
-        Identifier::Nickname(user)
-    };
-    let tokens = profile_controller.get_created_tokens(&identifier).await?;
-    Ok(Json(CreatedTokensResponse { tokens }))
+    let (tokens, next_cursor) = profile_controller
+        .get_created_tokens(
+            &account.account_id,
+            query.sort.unwrap_or_default(),
+            &pagination,
+        )
+        .await?;
+    Ok(Json(CreatedTokensResponse {
+        tokens,
+        next_cursor,
+    }))
 }
 
 /// Get user's followers
@@ -166,27 +282,36 @@ pub async fn get_created_tokens(
     get,
     path = ProfilePath::Followers.docs_str(),
     params(
-        ("user" = String, Path, description = "User's nickname or Ethereum address")
+        ("user" = String, Path, description = "User's nickname or Ethereum address"),
+        FollowQuery,
+        PaginationQuery
     ),
     responses(
         (status = 200, description = "User's followers retrieved successfully", body = FollowersResponse),
+        (status = 400, description = "Invalid query"),
         (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Profile"
 )]
 pub async fn get_followers(
-    Path(user): Path<String>,
+    account: ResolvedAccount,
+    Query(query): Query<FollowQuery>,
+    pagination: Pagination,
     State(state): State<AppState>,
 ) -> AppJsonResult<FollowersResponse> {
     let profile_controller = ProfileController::new(state.postgres.clone());
-    let identifier = if is_address(&user) {
-        Identifier::Address(user)
-    } else {
-        Identifier::Nickname(user)
-    };
-    let followers = profile_controller.get_followers(&identifier).await?;
-    Ok(Json(FollowersResponse { followers }))
+    let (followers, next_cursor) = profile_controller
+        .get_followers(
+            &account.account_id,
+            query.sort.unwrap_or_default(),
+            &pagination,
+        )
+        .await?;
+    Ok(Json(FollowersResponse {
+        followers,
+        next_cursor,
+    }))
 }
 
 /// Get accounts followed by user
@@ -194,25 +319,277 @@ pub async fn get_followers(
     get,
     path = ProfilePath::Following.docs_str(),
     params(
-        ("user" = String, Path, description = "User's nickname or Ethereum address")
+        ("user" = String, Path, description = "User's nickname or Ethereum address"),
+        FollowQuery,
+        PaginationQuery
     ),
     responses(
         (status = 200, description = "Followed accounts retrieved successfully", body = FollowingResponse),
+        (status = 400, description = "Invalid query"),
         (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Profile"
 )]
 pub async fn get_following(
-    Path(user): Path<String>,
+    account: ResolvedAccount,
+    Query(query): Query<FollowQuery>,
+    pagination: Pagination,
     State(state): State<AppState>,
 ) -> AppJsonResult<FollowingResponse> {
     let profile_controller = ProfileController::new(state.postgres.clone());
-    let identifier = if is_address(&user) {
-        Identifier::Address(user)
-    } else {
-        Identifier::Nickname(user)
-    };
-    let following = profile_controller.get_following(&identifier).await?;
-    Ok(Json(FollowingResponse { following }))
+    let (following, next_cursor) = profile_controller
+        .get_following(
+            &account.account_id,
+            query.sort.unwrap_or_default(),
+            &pagination,
+        )
+        .await?;
+    Ok(Json(FollowingResponse {
+        following,
+        next_cursor,
+    }))
+}
+
+/// Get user's trade history
+#[utoipa::path(
+    get,
+    path = ProfilePath::Trades.docs_str(),
+    params(
+        ("user" = String, Path, description = "User's nickname or Ethereum address"),
+        TradeQuery
+    ),
+    responses(
+        (status = 200, description = "User's trades retrieved successfully", body = TradesResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Profile"
+)]
+pub async fn get_trades(
+    account: ResolvedAccount,
+    Query(query): Query<TradeQuery>,
+    State(state): State<AppState>,
+) -> AppJsonResult<TradesResponse> {
+    let filter = TradeFilter::try_from(query).map_err(AppError::BadRequest)?;
+    let trade_controller = TradeController::new(state.postgres.clone());
+    let trades = trade_controller
+        .get_account_trades(&account.account_id, &filter)
+        .await?;
+    Ok(Json(trades))
+}
+
+/// Get accounts that liked user
+#[utoipa::path(
+    get,
+    path = ProfilePath::LikesReceived.docs_str(),
+    params(
+        ("user" = String, Path, description = "User's nickname or Ethereum address"),
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "User's likers retrieved successfully", body = LikersResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Profile"
+)]
+pub async fn get_likes_received(
+    account: ResolvedAccount,
+    pagination: Pagination<i32>,
+    State(state): State<AppState>,
+) -> AppJsonResult<LikersResponse> {
+    let like_controller = LikeController::new(state.postgres.clone());
+    let likers = like_controller
+        .get_likes_received(&account.account_id, &pagination)
+        .await?;
+    Ok(Json(likers))
+}
+
+/// Get threads liked by user
+#[utoipa::path(
+    get,
+    path = ProfilePath::LikesGiven.docs_str(),
+    params(
+        ("user" = String, Path, description = "User's nickname or Ethereum address"),
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "Threads liked by user retrieved successfully", body = LikedThreadsResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Profile"
+)]
+pub async fn get_likes_given(
+    account: ResolvedAccount,
+    pagination: Pagination,
+    State(state): State<AppState>,
+) -> AppJsonResult<LikedThreadsResponse> {
+    let like_controller = LikeController::new(state.postgres.clone());
+    let threads = like_controller
+        .get_liked_threads(&account.account_id, &pagination)
+        .await?;
+    Ok(Json(threads))
+}
+
+/// Get accounts liked by user
+#[utoipa::path(
+    get,
+    path = ProfilePath::LikedAccounts.docs_str(),
+    params(
+        ("user" = String, Path, description = "User's nickname or Ethereum address"),
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "Accounts liked by user retrieved successfully", body = LikedAccountsResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Profile"
+)]
+pub async fn get_liked_accounts(
+    account: ResolvedAccount,
+    pagination: Pagination<i32>,
+    State(state): State<AppState>,
+) -> AppJsonResult<LikedAccountsResponse> {
+    let like_controller = LikeController::new(state.postgres.clone());
+    let accounts = like_controller
+        .get_liked_accounts(&account.account_id, &pagination)
+        .await?;
+    Ok(Json(accounts))
+}
+
+/// Follow user
+#[utoipa::path(
+    post,
+    path = ProfilePath::Follow.docs_str(),
+    params(
+        ("user" = String, Path, description = "User's nickname or Ethereum address")
+    ),
+    responses(
+        (status = 204, description = "User followed"),
+        (status = 400, description = "Target is the signed-in account"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "Already following user"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Profile"
+)]
+pub async fn follow_account(
+    session: AuthSession,
+    target: ResolvedAccount,
+    State(state): State<AppState>,
+) -> AppResult<StatusCode> {
+    ensure_not_self(&target, &session)?;
+    let follow_controller = FollowController::new(state.postgres.clone());
+    if !follow_controller
+        .follow(&session.account_id, &target.account_id)
+        .await?
+    {
+        return Err(AppError::Conflict);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Unfollow user
+#[utoipa::path(
+    delete,
+    path = ProfilePath::Follow.docs_str(),
+    params(
+        ("user" = String, Path, description = "User's nickname or Ethereum address")
+    ),
+    responses(
+        (status = 204, description = "User unfollowed"),
+        (status = 400, description = "Target is the signed-in account"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 404, description = "User not found or not followed"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Profile"
+)]
+pub async fn unfollow_account(
+    session: AuthSession,
+    target: ResolvedAccount,
+    State(state): State<AppState>,
+) -> AppResult<StatusCode> {
+    ensure_not_self(&target, &session)?;
+    let follow_controller = FollowController::new(state.postgres.clone());
+    if !follow_controller
+        .unfollow(&session.account_id, &target.account_id)
+        .await?
+    {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Like user
+#[utoipa::path(
+    post,
+    path = ProfilePath::Like.docs_str(),
+    params(
+        ("user" = String, Path, description = "User's nickname or Ethereum address")
+    ),
+    responses(
+        (status = 204, description = "User liked"),
+        (status = 400, description = "Target is the signed-in account"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "Already liked user"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Profile"
+)]
+pub async fn like_account(
+    session: AuthSession,
+    target: ResolvedAccount,
+    State(state): State<AppState>,
+) -> AppResult<StatusCode> {
+    ensure_not_self(&target, &session)?;
+    let like_controller = LikeController::new(state.postgres.clone());
+    if !like_controller
+        .like_account(&session.account_id, &target.account_id)
+        .await?
+    {
+        return Err(AppError::Conflict);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Unlike user
+#[utoipa::path(
+    delete,
+    path = ProfilePath::Like.docs_str(),
+    params(
+        ("user" = String, Path, description = "User's nickname or Ethereum address")
+    ),
+    responses(
+        (status = 204, description = "User unliked"),
+        (status = 400, description = "Target is the signed-in account"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 404, description = "User not found or not liked"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Profile"
+)]
+pub async fn unlike_account(
+    session: AuthSession,
+    target: ResolvedAccount,
+    State(state): State<AppState>,
+) -> AppResult<StatusCode> {
+    ensure_not_self(&target, &session)?;
+    let like_controller = LikeController::new(state.postgres.clone());
+    if !like_controller
+        .unlike_account(&session.account_id, &target.account_id)
+        .await?
+    {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
 }
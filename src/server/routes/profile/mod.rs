@@ -2,18 +2,37 @@ pub mod handler;
 pub mod path;
 use crate::server::state::AppState;
 
-use axum::{routing::get, Router};
+use axum::{
+    routing::{get, patch, post},
+    Router,
+};
 use handler::{
-    get_created_tokens, get_followers, get_following, get_profile, get_replies, get_tokens_held,
+    follow_account, get_created_tokens, get_followers, get_following, get_liked_accounts,
+    get_likes_given, get_likes_received, get_portfolio, get_profile, get_replies, get_tokens_held,
+    get_trades, like_account, unfollow_account, unlike_account, update_profile,
 };
 use path::ProfilePath;
 
 pub fn router() -> Router<AppState> {
     Router::new()
         .route(ProfilePath::Profile.as_str(), get(get_profile))
+        .route(ProfilePath::Edit.as_str(), patch(update_profile))
         .route(ProfilePath::TokenCreated.as_str(), get(get_created_tokens))
         .route(ProfilePath::TokenHeld.as_str(), get(get_tokens_held))
+        .route(ProfilePath::Portfolio.as_str(), get(get_portfolio))
         .route(ProfilePath::Replies.as_str(), get(get_replies))
         .route(ProfilePath::Followers.as_str(), get(get_followers))
         .route(ProfilePath::Following.as_str(), get(get_following))
+        .route(ProfilePath::Trades.as_str(), get(get_trades))
+        .route(ProfilePath::LikesReceived.as_str(), get(get_likes_received))
+        .route(ProfilePath::LikesGiven.as_str(), get(get_likes_given))
+        .route(ProfilePath::LikedAccounts.as_str(), get(get_liked_accounts))
+        .route(
+            ProfilePath::Follow.as_str(),
+            post(follow_account).delete(unfollow_account),
+        )
+        .route(
+            ProfilePath::Like.as_str(),
+            post(like_account).delete(unlike_account),
+        )
 }
@@ -1,33 +1,57 @@
 #[derive(Debug)]
 pub enum ProfilePath {
     Profile,
+    Edit,
     TokenHeld,
+    Portfolio,
     Replies,
     TokenCreated,
     Followers,
     Following,
+    Trades,
+    LikesReceived,
+    LikesGiven,
+    LikedAccounts,
+    Follow,
+    Like,
 }
 
 impl ProfilePath {
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Profile => "/profile/:user",
+            Self::Edit => "/profile",
             Self::TokenHeld => "/profile/tokens-held/:user",
+            Self::Portfolio => "/profile/portfolio/:user",
             Self::Replies => "/profile/replies/:user",
             Self::TokenCreated => "/profile/tokens-created/:user",
             Self::Followers => "/profile/followers/:user",
             Self::Following => "/profile/following/:user",
+            Self::Trades => "/profile/trades/:user",
+            Self::LikesReceived => "/profile/likes-received/:user",
+            Self::LikesGiven => "/profile/likes-given/:user",
+            Self::LikedAccounts => "/profile/liked-accounts/:user",
+            Self::Follow => "/profile/follow/:user",
+            Self::Like => "/profile/like/:user",
         }
     }
 
     pub fn docs_str(&self) -> &'static str {
         match self {
             Self::Profile => "/profile/{user}",
+            Self::Edit => "/profile",
             Self::TokenHeld => "/profile/tokens-held/{user}",
+            Self::Portfolio => "/profile/portfolio/{user}",
             Self::Replies => "/profile/replies/{user}",
             Self::TokenCreated => "/profile/tokens-created/{user}",
             Self::Followers => "/profile/followers/{user}",
             Self::Following => "/profile/following/{user}",
+            Self::Trades => "/profile/trades/{user}",
+            Self::LikesReceived => "/profile/likes-received/{user}",
+            Self::LikesGiven => "/profile/likes-given/{user}",
+            Self::LikedAccounts => "/profile/liked-accounts/{user}",
+            Self::Follow => "/profile/follow/{user}",
+            Self::Like => "/profile/like/{user}",
         }
     }
 }
@@ -1,31 +1,33 @@
-use axum::extract::Path;
+use axum::extract::{Path, Query};
 use axum::{extract::State, Json};
 use serde::{Deserialize, Serialize};
 use tracing::{info, instrument};
 use utoipa::ToSchema;
 
 use super::path::Path as SearchPath;
-use crate::db::postgres::controller::order::OrderController;
-use crate::server::result::AppJsonResult;
+use crate::db::postgres::controller::search::SearchController;
+use crate::server::result::{AppError, AppJsonResult};
 use crate::types::event::order::OrderTokenResponse;
+use crate::types::model::Account;
+use crate::types::search::{
+    is_transaction_hash, SearchFilter, SearchQuery, TransactionHit, UnifiedSearchQuery,
+    UNIFIED_SEARCH_LIMIT,
+};
 use crate::{server::state::AppState, types::model::Token};
 
 #[derive(Debug, Serialize, ToSchema)]
-#[schema(example = json!({
-    "token_name": "token name or symbol"
-}))]
 pub struct SearchResponse {
-    // #[schema(example = "token_name or symbol")]
     tokens: Vec<OrderTokenResponse>,
 }
 #[utoipa::path(
     get,
     path = SearchPath::Search.docs_str(),
     params(
-        ("token" = String, Path, description = "Token to search for")
+        ("token" = String, Path, description = "Name, symbol, description words or contract address prefix"),
+        SearchQuery
     ),
     responses(
-        (status = 200, description = "Nonce generated successfully", body = SearchResponse),
+        (status = 200, description = "Matching tokens retrieved successfully", body = SearchResponse),
         (status = 400, description = "Bad request"),
         (status = 500, description = "Internal server error")
     ),
@@ -34,9 +36,67 @@ pub struct SearchResponse {
 #[instrument(skip(state))]
 pub async fn search_token(
     Path(token): Path<String>,
+    Query(query): Query<SearchQuery>,
     State(state): State<AppState>,
 ) -> AppJsonResult<SearchResponse> {
-    let order_contoller = OrderController::new(state.postgres.clone());
-    let tokens = order_contoller.search_order_tokens(&token).await?;
+    let filter = SearchFilter::new(&token, query).map_err(AppError::BadRequest)?;
+    let search_controller = SearchController::new(state.postgres.clone());
+    let tokens = search_controller.search_tokens(&filter).await?;
     Ok(Json(SearchResponse { tokens }))
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnifiedSearchResponse {
+    tokens: Vec<OrderTokenResponse>,
+    accounts: Vec<Account>,
+    /// Only filled when `q` is a transaction hash
+    transactions: Vec<TransactionHit>,
+}
+
+/// Search tokens, accounts and transactions at once
+#[utoipa::path(
+    get,
+    path = SearchPath::Unified.docs_str(),
+    params(UnifiedSearchQuery),
+    responses(
+        (status = 200, description = "Search results retrieved successfully", body = UnifiedSearchResponse),
+        (status = 400, description = "Bad request"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Search Token"
+)]
+#[instrument(skip(state))]
+pub async fn search(
+    Query(query): Query<UnifiedSearchQuery>,
+    State(state): State<AppState>,
+) -> AppJsonResult<UnifiedSearchResponse> {
+    let filter = SearchFilter::new(
+        &query.q,
+        SearchQuery {
+            sort: None,
+            limit: Some(query.limit.unwrap_or(UNIFIED_SEARCH_LIMIT)),
+        },
+    )
+    .map_err(AppError::BadRequest)?;
+    let search_controller = SearchController::new(state.postgres.clone());
+
+    // A pasted hash can't match a token or an account
+    if is_transaction_hash(&filter.term) {
+        let transactions = search_controller.search_transactions(&filter.term).await?;
+        return Ok(Json(UnifiedSearchResponse {
+            tokens: vec![],
+            accounts: vec![],
+            transactions,
+        }));
+    }
+
+    let (tokens, accounts) = tokio::try_join!(
+        search_controller.search_tokens(&filter),
+        search_controller.search_accounts(&filter)
+    )?;
+    Ok(Json(UnifiedSearchResponse {
+        tokens,
+        accounts,
+        transactions: vec![],
+    }))
+}
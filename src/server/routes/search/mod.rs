@@ -2,11 +2,13 @@ pub mod handler;
 pub mod path;
 use axum::{routing::get, Router};
 
-use handler::search_token;
+use handler::{search, search_token};
 use path::Path;
 
 use crate::server::state::AppState;
 
 pub fn router() -> Router<AppState> {
-    Router::new().route(Path::Search.as_str(), get(search_token))
+    Router::new()
+        .route(Path::Search.as_str(), get(search_token))
+        .route(Path::Unified.as_str(), get(search))
 }
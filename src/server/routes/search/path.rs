@@ -1,16 +1,19 @@
 pub enum Path {
     Search,
+    Unified,
 }
 
 impl Path {
     pub fn as_str(&self) -> &'static str {
         match self {
             Path::Search => "/search/:token",
+            Path::Unified => "/search",
         }
     }
     pub fn docs_str(&self) -> &'static str {
         match self {
             Path::Search => "/search/{token}",
+            Path::Unified => "/search",
         }
     }
 }
@@ -24,9 +24,11 @@ use tokio::{
 use tracing::{error, info};
 
 use crate::server::{
+    auth::AuthSession,
     routes::socket::{
-        json_rpc::{send_error_response, JsonRpcErrorCode},
-        subscribe::handle_new_content_subscribe,
+        json_rpc::{send_error_response, send_error_response_with_id, JsonRpcErrorCode},
+        search::handle_search,
+        subscribe::{handle_king_subscribe, handle_new_content_subscribe},
     },
     state::AppState,
 };
@@ -39,11 +41,27 @@ enum ActiveSubscription {
     Order(JoinHandle<()>),
     Token(JoinHandle<()>),
     NewContent(JoinHandle<()>),
+    King(JoinHandle<()>),
     None,
 }
+
+impl ActiveSubscription {
+    /// Aborts whichever subscription task is running, regardless of its kind.
+    fn cancel(&mut self) {
+        if let ActiveSubscription::Order(handle)
+        | ActiveSubscription::Token(handle)
+        | ActiveSubscription::NewContent(handle)
+        | ActiveSubscription::King(handle) = std::mem::replace(self, ActiveSubscription::None)
+        {
+            handle.abort();
+        }
+    }
+}
+
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
     user_agent: Option<TypedHeader<headers::UserAgent>>,
+    session: Option<AuthSession>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     State(state): State<AppState>,
 ) -> impl IntoResponse {
@@ -54,11 +72,22 @@ pub async fn ws_handler(
         String::from("Unknown browser")
     };
 
-    ws.on_upgrade(move |socket| handle_socket(socket, addr, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, addr, session, state))
 }
 
-pub async fn handle_socket(socket: WebSocket, addr: SocketAddr, state: AppState) {
-    info!("New WebSocket connection: {}", addr);
+pub async fn handle_socket(
+    socket: WebSocket,
+    addr: SocketAddr,
+    session: Option<AuthSession>,
+    state: AppState,
+) {
+    match &session {
+        Some(session) => info!(
+            "New WebSocket connection: {} (account: {})",
+            addr, session.account_id
+        ),
+        None => info!("New WebSocket connection: {}", addr),
+    }
     let (mut sender, mut receiver) = socket.split();
     let (tx, mut rx) = mpsc::channel::<Message>(100);
     let (close_tx, close_rx) = oneshot::channel();
@@ -75,9 +104,16 @@ pub async fn handle_socket(socket: WebSocket, addr: SocketAddr, state: AppState)
     let tx_clone = tx.clone();
     let mut recv_task = tokio::spawn(async move {
         let mut active_subscription = ActiveSubscription::None;
+        let mut active_search: Option<JoinHandle<()>> = None;
         while let Some(Ok(message)) = receiver.next().await {
-            if let Err(e) =
-                handle_message(message, &state_clone, &tx_clone, &mut active_subscription).await
+            if let Err(e) = handle_message(
+                message,
+                &state_clone,
+                &tx_clone,
+                &mut active_subscription,
+                &mut active_search,
+            )
+            .await
             {
                 if let Err(send_err) =
                     send_error_response(&tx_clone, JsonRpcErrorCode::InternalError, &e.to_string())
@@ -89,10 +125,8 @@ pub async fn handle_socket(socket: WebSocket, addr: SocketAddr, state: AppState)
             }
         }
         // Cancel the active subscription if any
-        if let ActiveSubscription::Order(handle)
-        | ActiveSubscription::Token(handle)
-        | ActiveSubscription::NewContent(handle) = active_subscription
-        {
+        active_subscription.cancel();
+        if let Some(handle) = active_search {
             handle.abort();
         }
         let _ = close_tx.send(());
@@ -115,6 +149,7 @@ async fn handle_message(
     state: &AppState,
     tx: &Sender<Message>,
     active_subscription: &mut ActiveSubscription,
+    active_search: &mut Option<JoinHandle<()>>,
 ) -> Result<()> {
     match msg {
         Message::Text(text) => {
@@ -126,37 +161,55 @@ async fn handle_message(
             match request.method() {
                 JsonRpcMethod::OrderSubscribe => {
                     // Cancel any existing subscription
-                    if let ActiveSubscription::Order(handle) =
-                        std::mem::replace(active_subscription, ActiveSubscription::None)
-                    {
-                        handle.abort();
-                    }
+                    active_subscription.cancel();
                     let new_handle = handle_order_subscribe(request, state, tx.clone()).await?;
                     *active_subscription = ActiveSubscription::Order(new_handle);
                     Ok(())
                 }
                 JsonRpcMethod::TokenSubscribe => {
                     // Cancel any existing subscription
-                    if let ActiveSubscription::Token(handle) =
-                        std::mem::replace(active_subscription, ActiveSubscription::None)
-                    {
-                        handle.abort();
-                    }
+                    active_subscription.cancel();
                     let new_handle = handle_token_subscribe(request, state, tx.clone()).await?;
                     *active_subscription = ActiveSubscription::Token(new_handle);
                     Ok(())
                 }
                 JsonRpcMethod::NewContentSubscribe => {
-                    if let ActiveSubscription::NewContent(handle) =
-                        std::mem::replace(active_subscription, ActiveSubscription::None)
-                    {
-                        handle.abort();
-                    }
+                    // Cancel any existing subscription
+                    active_subscription.cancel();
                     let new_handle =
                         handle_new_content_subscribe(request, state, tx.clone()).await?;
                     *active_subscription = ActiveSubscription::NewContent(new_handle);
                     Ok(())
                 }
+                JsonRpcMethod::KingSubscribe => {
+                    // Cancel any existing subscription
+                    active_subscription.cancel();
+                    let new_handle = handle_king_subscribe(request, state, tx.clone()).await?;
+                    *active_subscription = ActiveSubscription::King(new_handle);
+                    Ok(())
+                }
+                JsonRpcMethod::Search => {
+                    // Searches run alongside the subscription; only the latest one is answered
+                    if let Some(handle) = active_search.take() {
+                        handle.abort();
+                    }
+                    let id = request.id().cloned();
+                    match handle_search(request, state, tx.clone()) {
+                        Ok(handle) => {
+                            *active_search = Some(handle);
+                            Ok(())
+                        }
+                        Err(e) => {
+                            send_error_response_with_id(
+                                tx,
+                                JsonRpcErrorCode::InvalidParams,
+                                id,
+                                &e.to_string(),
+                            )
+                            .await
+                        }
+                    }
+                }
                 _ => {
                     send_error_response(tx, JsonRpcErrorCode::MethodNotFound, "Unknown method")
                         .await
@@ -10,6 +10,8 @@ pub struct JsonRpcRequest {
     jsonrpc: String,
     method: JsonRpcMethod,
     params: Option<Value>,
+    /// Echoed back on responses so clients can match them to requests
+    id: Option<Value>,
 }
 impl JsonRpcRequest {
     pub fn method(&self) -> &JsonRpcMethod {
@@ -18,6 +20,9 @@ impl JsonRpcRequest {
     pub fn params(&self) -> Option<&Value> {
         self.params.as_ref()
     }
+    pub fn id(&self) -> Option<&Value> {
+        self.id.as_ref()
+    }
 }
 fn default_jsonrpc() -> String {
     "2.0".to_string()
@@ -29,6 +34,9 @@ pub enum JsonRpcMethod {
     OrderSubscribe,
     TokenSubscribe,
     NewContentSubscribe,
+    KingSubscribe,
+    /// One-shot token suggestions; a newer search cancels the pending one
+    Search,
     // 다른 메서드들을 여기에 추가할 수 있습니다.
 }
 
@@ -39,10 +47,14 @@ pub enum JsonRpcResponse {
         jsonrpc: String,
         method: JsonRpcMethod,
         result: Option<Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<Value>,
     },
     Error {
         jsonrpc: String,
         error: JsonRpcError,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<Value>,
     },
 }
 
@@ -85,11 +97,21 @@ pub async fn send_success_response(
     tx: &Sender<Message>,
     method: &JsonRpcMethod,
     result: Value,
+) -> Result<()> {
+    send_success_response_with_id(tx, method, None, result).await
+}
+
+pub async fn send_success_response_with_id(
+    tx: &Sender<Message>,
+    method: &JsonRpcMethod,
+    id: Option<Value>,
+    result: Value,
 ) -> Result<()> {
     let response = JsonRpcResponse::Success {
         jsonrpc: "2.0".to_string(),
         method: method.to_owned(),
         result: Some(result),
+        id,
     };
     // info!("Sending response: {:?}", response);
     send_response(tx, response).await
@@ -99,6 +121,15 @@ pub async fn send_error_response(
     tx: &Sender<Message>,
     code: JsonRpcErrorCode,
     message: &str,
+) -> Result<()> {
+    send_error_response_with_id(tx, code, None, message).await
+}
+
+pub async fn send_error_response_with_id(
+    tx: &Sender<Message>,
+    code: JsonRpcErrorCode,
+    id: Option<Value>,
+    message: &str,
 ) -> Result<()> {
     let response = JsonRpcResponse::Error {
         jsonrpc: "2.0".to_string(),
@@ -106,6 +137,7 @@ pub async fn send_error_response(
             code,
             message: message.to_string(),
         },
+        id,
     };
     send_response(tx, response).await
 }
@@ -1,5 +1,6 @@
 pub mod handler;
 pub mod json_rpc;
+pub mod search;
 pub mod subscribe;
 
 use axum::{routing::get, Router};
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use axum::extract::ws::Message;
+use serde_json::Value;
+use tokio::{sync::mpsc::Sender, task::JoinHandle, time::sleep};
+use tracing::error;
+
+use crate::{
+    server::state::AppState,
+    types::event::search::{SearchMessage, MAX_SUGGESTION_LIMIT, SUGGESTION_LIMIT},
+};
+
+use super::json_rpc::{
+    send_error_response_with_id, send_success_response_with_id, JsonRpcErrorCode, JsonRpcRequest,
+};
+
+/// Wait before searching, so keystrokes that arrive in between cancel this query for free
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Answers a `search` request in the background. The caller aborts the returned task
+/// when a newer query supersedes it, so stale suggestions are never sent.
+///
+/// Params are the query string or `{"query": "...", "limit": 10}`.
+pub fn handle_search(
+    request: JsonRpcRequest,
+    state: &AppState,
+    tx: Sender<Message>,
+) -> Result<JoinHandle<()>> {
+    let query = parse_query(request.params()).context("Invalid or missing query")?;
+    let limit = parse_limit(request.params())
+        .unwrap_or(SUGGESTION_LIMIT)
+        .clamp(1, MAX_SUGGESTION_LIMIT);
+    let search_index = state.search_index.clone();
+
+    let handle = tokio::spawn(async move {
+        sleep(SEARCH_DEBOUNCE).await;
+        let id = request.id().cloned();
+
+        let result = match search_index.search(&query, limit).await {
+            Ok(tokens) => serde_json::to_value(SearchMessage { query, tokens })
+                .context("Failed to serialize search result"),
+            Err(e) => Err(e),
+        };
+        let sent = match result {
+            Ok(result) => send_success_response_with_id(&tx, request.method(), id, result).await,
+            Err(e) => {
+                error!("Failed to search tokens: {:?}", e);
+                send_error_response_with_id(
+                    &tx,
+                    JsonRpcErrorCode::InternalError,
+                    id,
+                    &e.to_string(),
+                )
+                .await
+            }
+        };
+        if let Err(e) = sent {
+            error!("Failed to send search result: {:?}", e);
+        }
+    });
+
+    Ok(handle)
+}
+
+fn parse_query(params: Option<&Value>) -> Option<String> {
+    match params {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(Value::Object(obj)) => obj
+            .get("query")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_owned()),
+        _ => None,
+    }
+}
+
+fn parse_limit(params: Option<&Value>) -> Option<usize> {
+    match params {
+        Some(Value::Object(obj)) => obj
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|limit| limit as usize),
+        _ => None,
+    }
+}
@@ -12,11 +12,13 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
+use crate::db::postgres::controller::king::KingController;
 use crate::db::postgres::controller::tokenpage::TokenPageController;
 
 use crate::server::state::AppState;
 
 use crate::types::chart_type::ChartType;
+use crate::types::event::king::KingMessage;
 use crate::types::event::new_content::NewContentMessage;
 use crate::types::event::order::OrderMessage;
 use crate::types::event::order::OrderType;
@@ -213,6 +215,39 @@ pub async fn handle_new_content_subscribe(
     Ok(handle)
 }
 
+pub async fn handle_king_subscribe(
+    request: JsonRpcRequest,
+    state: &AppState,
+    tx: Sender<Message>,
+) -> Result<JoinHandle<()>> {
+    info!("King subscribe");
+    let king_controller = KingController::new(state.postgres.clone());
+    let king = king_controller
+        .get_current_king()
+        .await
+        .context("Failed to get current king")?;
+
+    let message_json =
+        serde_json::to_value(KingMessage { king }).context("Failed to serialize king")?;
+    send_success_response(&tx, request.method(), json!(message_json)).await?;
+    let mut receiver = state.king_event_producer.get_king_receiver().await;
+
+    let handle = tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            info!("King message: {:?}", message);
+
+            if let Err(e) = send_success_response(&tx, request.method(), json!(message)).await {
+                error!("Failed to send king event: {:?}", e);
+                break;
+            }
+        }
+        // Ensure receiver is dropped here
+        drop(receiver);
+    });
+
+    Ok(handle)
+}
+
 fn parse_order_type(params: Option<&Value>) -> Option<OrderType> {
     match params {
         Some(Value::String(s)) => OrderType::from_str(s).ok(),
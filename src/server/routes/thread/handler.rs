@@ -0,0 +1,212 @@
+use crate::{
+    db::postgres::controller::{like::LikeController, thread::ThreadController},
+    server::{
+        auth::AuthSession,
+        result::{AppError, AppJsonResult, AppResult},
+        state::AppState,
+    },
+    types::{
+        like::LikersResponse,
+        model::Thread,
+        pagination::{parse_cursor, Pagination, PaginationQuery},
+        thread::{
+            ReplyQuery, ThreadRepliesResponse, UpdateThreadRequest, MAX_REPLY_LIMIT, REPLY_LIMIT,
+        },
+    },
+};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+
+use super::path::ThreadPath;
+
+/// Loads the thread, allowing only its author through
+async fn get_own_thread(id: i32, session: &AuthSession, state: &AppState) -> AppResult<Thread> {
+    let thread_controller = ThreadController::new(state.postgres.clone());
+    let thread = thread_controller
+        .get_thread(id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if thread.author_id != session.account_id {
+        return Err(AppError::Forbidden("Not the author of this thread".into()));
+    }
+    Ok(thread)
+}
+
+/// Edit a thread
+#[utoipa::path(
+    patch,
+    path = ThreadPath::Thread.docs_str(),
+    params(
+        ("id" = i32, Path, description = "Thread id")
+    ),
+    request_body = UpdateThreadRequest,
+    responses(
+        (status = 200, description = "Thread updated successfully", body = Thread),
+        (status = 400, description = "Invalid content or image uri"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 403, description = "Not the author"),
+        (status = 404, description = "Thread not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Thread"
+)]
+pub async fn update_thread(
+    session: AuthSession,
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+    Json(request): Json<UpdateThreadRequest>,
+) -> AppJsonResult<Thread> {
+    request.validate().map_err(AppError::BadRequest)?;
+    get_own_thread(id, &session, &state).await?;
+    let thread_controller = ThreadController::new(state.postgres.clone());
+    let thread = thread_controller.update_thread(id, &request).await?;
+    Ok(Json(thread))
+}
+
+/// Delete a thread along with its replies
+#[utoipa::path(
+    delete,
+    path = ThreadPath::Thread.docs_str(),
+    params(
+        ("id" = i32, Path, description = "Thread id")
+    ),
+    responses(
+        (status = 204, description = "Thread deleted"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 403, description = "Not the author"),
+        (status = 404, description = "Thread not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Thread"
+)]
+pub async fn delete_thread(
+    session: AuthSession,
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> AppResult<StatusCode> {
+    let thread = get_own_thread(id, &session, &state).await?;
+    let thread_controller = ThreadController::new(state.postgres.clone());
+    thread_controller.delete_thread(&thread).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get replies to a thread
+#[utoipa::path(
+    get,
+    path = ThreadPath::Replies.docs_str(),
+    params(
+        ("id" = i32, Path, description = "Root thread id"),
+        ReplyQuery
+    ),
+    responses(
+        (status = 200, description = "Replies retrieved successfully", body = ThreadRepliesResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Thread"
+)]
+pub async fn get_thread_replies(
+    Path(id): Path<i32>,
+    Query(query): Query<ReplyQuery>,
+    State(state): State<AppState>,
+) -> AppJsonResult<ThreadRepliesResponse> {
+    let cursor = parse_cursor(query.cursor).map_err(AppError::BadRequest)?;
+    let limit = query.limit.unwrap_or(REPLY_LIMIT).clamp(1, MAX_REPLY_LIMIT);
+    let thread_controller = ThreadController::new(state.postgres.clone());
+    let replies = thread_controller
+        .get_thread_replies(id, cursor, limit)
+        .await?;
+    Ok(Json(replies))
+}
+
+/// Get accounts that liked a thread
+#[utoipa::path(
+    get,
+    path = ThreadPath::Likers.docs_str(),
+    params(
+        ("id" = i32, Path, description = "Thread id"),
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "Likers retrieved successfully", body = LikersResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Thread"
+)]
+pub async fn get_thread_likers(
+    Path(id): Path<i32>,
+    pagination: Pagination,
+    State(state): State<AppState>,
+) -> AppJsonResult<LikersResponse> {
+    let like_controller = LikeController::new(state.postgres.clone());
+    let likers = like_controller.get_thread_likers(id, &pagination).await?;
+    Ok(Json(likers))
+}
+
+/// Like a thread
+#[utoipa::path(
+    post,
+    path = ThreadPath::Like.docs_str(),
+    params(
+        ("id" = i32, Path, description = "Thread id")
+    ),
+    responses(
+        (status = 204, description = "Thread liked"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 404, description = "Thread not found"),
+        (status = 409, description = "Already liked thread"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Thread"
+)]
+pub async fn like_thread(
+    session: AuthSession,
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> AppResult<StatusCode> {
+    let thread_controller = ThreadController::new(state.postgres.clone());
+    thread_controller
+        .get_thread(id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    let like_controller = LikeController::new(state.postgres.clone());
+    if !like_controller.like_thread(id, &session.account_id).await? {
+        return Err(AppError::Conflict);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Unlike a thread
+#[utoipa::path(
+    delete,
+    path = ThreadPath::Like.docs_str(),
+    params(
+        ("id" = i32, Path, description = "Thread id")
+    ),
+    responses(
+        (status = 204, description = "Thread unliked"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 404, description = "Thread not found or not liked"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Thread"
+)]
+pub async fn unlike_thread(
+    session: AuthSession,
+    Path(id): Path<i32>,
+    State(state): State<AppState>,
+) -> AppResult<StatusCode> {
+    let like_controller = LikeController::new(state.postgres.clone());
+    if !like_controller
+        .unlike_thread(id, &session.account_id)
+        .await?
+    {
+        return Err(AppError::NotFound);
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,26 @@
+pub mod handler;
+pub mod path;
+use crate::server::state::AppState;
+
+use axum::{
+    routing::{get, patch, post},
+    Router,
+};
+use handler::{
+    delete_thread, get_thread_likers, get_thread_replies, like_thread, unlike_thread, update_thread,
+};
+use path::ThreadPath;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            ThreadPath::Thread.as_str(),
+            patch(update_thread).delete(delete_thread),
+        )
+        .route(ThreadPath::Replies.as_str(), get(get_thread_replies))
+        .route(ThreadPath::Likers.as_str(), get(get_thread_likers))
+        .route(
+            ThreadPath::Like.as_str(),
+            post(like_thread).delete(unlike_thread),
+        )
+}
@@ -0,0 +1,27 @@
+#[derive(Debug)]
+pub enum ThreadPath {
+    Thread,
+    Replies,
+    Likers,
+    Like,
+}
+
+impl ThreadPath {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Thread => "/thread/:id",
+            Self::Replies => "/thread/:id/replies",
+            Self::Likers => "/thread/:id/likers",
+            Self::Like => "/thread/:id/like",
+        }
+    }
+
+    pub fn docs_str(&self) -> &'static str {
+        match self {
+            Self::Thread => "/thread/{id}",
+            Self::Replies => "/thread/{id}/replies",
+            Self::Likers => "/thread/{id}/likers",
+            Self::Like => "/thread/{id}/like",
+        }
+    }
+}
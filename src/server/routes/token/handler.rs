@@ -0,0 +1,311 @@
+use std::str::FromStr;
+
+use crate::{
+    db::postgres::controller::{
+        thread::ThreadController,
+        token::{fill_chart_gaps, TokenController, CHART_LIMIT, MAX_CHART_LIMIT},
+        trade::TradeController,
+    },
+    server::{
+        auth::AuthSession,
+        result::{AppError, AppJsonResult, AppResult},
+        state::AppState,
+    },
+    types::{
+        chart_type::ChartType,
+        model::{Chart, Thread, Token},
+        thread::{CreateThreadRequest, ThreadFilter, ThreadQuery, ThreadsResponse},
+        token::{
+            HolderQuery, HoldersResponse, TokenDetail, UpdateTokenRequest, HOLDER_LIMIT,
+            MAX_HOLDER_LIMIT,
+        },
+        trade::{TradeFilter, TradeQuery, TradesResponse},
+    },
+};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use utoipa::{IntoParams, ToSchema};
+
+use super::path::TokenPath;
+
+#[derive(ToSchema, Serialize)]
+pub struct TokenDetailResponse {
+    token: TokenDetail,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ChartQuery {
+    /// Candle resolution: 1m, 5m, 15m, 30m, 1h, 4h or 1d
+    resolution: String,
+    /// Inclusive lower bound on `time_stamp`
+    from: Option<i64>,
+    /// Exclusive upper bound on `time_stamp`; pass the previous `next_cursor` to page backwards
+    to: Option<i64>,
+    /// Maximum number of candles to return
+    limit: Option<i64>,
+    /// Forward-fill empty buckets with flat candles at the previous close, up to `to`
+    fill: Option<bool>,
+}
+
+#[derive(ToSchema, Serialize)]
+pub struct ChartResponse {
+    resolution: String,
+    charts: Vec<Chart>,
+    next_cursor: Option<i64>,
+}
+
+/// Get token detail
+#[utoipa::path(
+    get,
+    path = TokenPath::Token.docs_str(),
+    params(
+        ("id" = String, Path, description = "Token contract address")
+    ),
+    responses(
+        (status = 200, description = "Token retrieved successfully", body = TokenDetailResponse),
+        (status = 404, description = "Token not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Token"
+)]
+pub async fn get_token(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> AppJsonResult<TokenDetailResponse> {
+    let token_controller = TokenController::new(state.postgres.clone());
+    let token = token_controller
+        .get_token_detail(&id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(TokenDetailResponse { token }))
+}
+
+/// Edit token metadata (creator only, once)
+#[utoipa::path(
+    patch,
+    path = TokenPath::Token.docs_str(),
+    params(
+        ("id" = String, Path, description = "Token contract address")
+    ),
+    request_body = UpdateTokenRequest,
+    responses(
+        (status = 200, description = "Token updated successfully", body = Token),
+        (status = 400, description = "Invalid description or url"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 403, description = "Not the creator"),
+        (status = 404, description = "Token not found"),
+        (status = 409, description = "Token was already updated"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Token"
+)]
+pub async fn update_token(
+    session: AuthSession,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    Json(request): Json<UpdateTokenRequest>,
+) -> AppJsonResult<Token> {
+    request.validate().map_err(AppError::BadRequest)?;
+    let token_controller = TokenController::new(state.postgres.clone());
+    let token = token_controller
+        .get_token(&id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    if token.creator != session.account_id {
+        return Err(AppError::Forbidden("Not the creator of this token".into()));
+    }
+    // Guarded in the update itself so concurrent edits can't both succeed
+    let token = token_controller
+        .update_token(&id, &session.account_id, &request)
+        .await?
+        .ok_or(AppError::Conflict)?;
+    Ok(Json(token))
+}
+
+/// Get token chart history
+#[utoipa::path(
+    get,
+    path = TokenPath::Chart.docs_str(),
+    params(
+        ("id" = String, Path, description = "Token contract address"),
+        ChartQuery
+    ),
+    responses(
+        (status = 200, description = "Chart retrieved successfully", body = ChartResponse),
+        (status = 400, description = "Invalid resolution"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Token"
+)]
+pub async fn get_charts(
+    Path(id): Path<String>,
+    Query(query): Query<ChartQuery>,
+    State(state): State<AppState>,
+) -> AppJsonResult<ChartResponse> {
+    let chart_type = ChartType::from_str(&query.resolution).map_err(AppError::BadRequest)?;
+    let limit = query.limit.unwrap_or(CHART_LIMIT).clamp(1, MAX_CHART_LIMIT);
+
+    let token_controller = TokenController::new(state.postgres.clone());
+    let charts = token_controller
+        .get_charts(&id, &chart_type, query.from, query.to, limit)
+        .await?;
+
+    let mut has_more = charts.len() as i64 == limit;
+    let charts = if query.fill.unwrap_or(false) {
+        // The dropped head is served by the next page, which fills up to `next_cursor`
+        let (filled, dropped) =
+            fill_chart_gaps(charts, chart_type.interval(), query.to, limit as usize);
+        has_more |= dropped;
+        filled
+    } else {
+        charts
+    };
+    let next_cursor = if has_more {
+        charts.first().map(|chart| chart.time_stamp)
+    } else {
+        None
+    };
+
+    Ok(Json(ChartResponse {
+        resolution: chart_type.to_string(),
+        charts,
+        next_cursor,
+    }))
+}
+
+/// Get token trade history
+#[utoipa::path(
+    get,
+    path = TokenPath::Trades.docs_str(),
+    params(
+        ("id" = String, Path, description = "Token contract address"),
+        TradeQuery
+    ),
+    responses(
+        (status = 200, description = "Trades retrieved successfully", body = TradesResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Token"
+)]
+pub async fn get_token_trades(
+    Path(id): Path<String>,
+    Query(query): Query<TradeQuery>,
+    State(state): State<AppState>,
+) -> AppJsonResult<TradesResponse> {
+    let filter = TradeFilter::try_from(query).map_err(AppError::BadRequest)?;
+    let trade_controller = TradeController::new(state.postgres.clone());
+    let trades = trade_controller.get_token_trades(&id, &filter).await?;
+    Ok(Json(trades))
+}
+
+/// Get token threads
+#[utoipa::path(
+    get,
+    path = TokenPath::Threads.docs_str(),
+    params(
+        ("id" = String, Path, description = "Token contract address"),
+        ThreadQuery
+    ),
+    responses(
+        (status = 200, description = "Threads retrieved successfully", body = ThreadsResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Token"
+)]
+pub async fn get_token_threads(
+    Path(id): Path<String>,
+    Query(query): Query<ThreadQuery>,
+    State(state): State<AppState>,
+) -> AppJsonResult<ThreadsResponse> {
+    let filter = ThreadFilter::try_from(query).map_err(AppError::BadRequest)?;
+    let thread_controller = ThreadController::new(state.postgres.clone());
+    let threads = thread_controller.get_token_threads(&id, &filter).await?;
+    Ok(Json(threads))
+}
+
+/// Post a thread or a reply on a token
+#[utoipa::path(
+    post,
+    path = TokenPath::Threads.docs_str(),
+    params(
+        ("id" = String, Path, description = "Token contract address")
+    ),
+    request_body = CreateThreadRequest,
+    responses(
+        (status = 201, description = "Thread created successfully", body = Thread),
+        (status = 400, description = "Invalid content, image uri or root thread"),
+        (status = 401, description = "Missing or invalid session"),
+        (status = 404, description = "Token not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Token"
+)]
+pub async fn create_thread(
+    session: AuthSession,
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    Json(request): Json<CreateThreadRequest>,
+) -> AppResult<(StatusCode, Json<Thread>)> {
+    request.validate().map_err(AppError::BadRequest)?;
+    let token_controller = TokenController::new(state.postgres.clone());
+    token_controller
+        .get_token(&id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let thread_controller = ThreadController::new(state.postgres.clone());
+    if let Some(root_id) = request.root_id {
+        let root = thread_controller.get_thread(root_id).await?;
+        if !root.is_some_and(|root| root.token_id == id && root.root_id.is_none()) {
+            return Err(AppError::BadRequest(format!(
+                "Thread {} is not a root thread of this token",
+                root_id
+            )));
+        }
+    }
+
+    let thread = thread_controller
+        .create_thread(&id, &session.account_id, &request)
+        .await?;
+    Ok((StatusCode::CREATED, Json(thread)))
+}
+
+/// Get token holder distribution
+#[utoipa::path(
+    get,
+    path = TokenPath::Holders.docs_str(),
+    params(
+        ("id" = String, Path, description = "Token contract address"),
+        HolderQuery
+    ),
+    responses(
+        (status = 200, description = "Holders retrieved successfully", body = HoldersResponse),
+        (status = 404, description = "Token not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Token"
+)]
+pub async fn get_holders(
+    Path(id): Path<String>,
+    Query(query): Query<HolderQuery>,
+    State(state): State<AppState>,
+) -> AppJsonResult<HoldersResponse> {
+    let limit = query
+        .limit
+        .unwrap_or(HOLDER_LIMIT)
+        .clamp(1, MAX_HOLDER_LIMIT);
+    let token_controller = TokenController::new(state.postgres.clone());
+    let holders = token_controller
+        .get_holders(&id, limit)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    Ok(Json(holders))
+}
@@ -0,0 +1,25 @@
+pub mod handler;
+pub mod path;
+use crate::server::state::AppState;
+
+use axum::{routing::get, Router};
+use handler::{
+    create_thread, get_charts, get_holders, get_token, get_token_threads, get_token_trades,
+    update_token,
+};
+use path::TokenPath;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            TokenPath::Token.as_str(),
+            get(get_token).patch(update_token),
+        )
+        .route(TokenPath::Chart.as_str(), get(get_charts))
+        .route(TokenPath::Trades.as_str(), get(get_token_trades))
+        .route(
+            TokenPath::Threads.as_str(),
+            get(get_token_threads).post(create_thread),
+        )
+        .route(TokenPath::Holders.as_str(), get(get_holders))
+}
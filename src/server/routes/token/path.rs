@@ -0,0 +1,30 @@
+#[derive(Debug)]
+pub enum TokenPath {
+    Token,
+    Chart,
+    Trades,
+    Threads,
+    Holders,
+}
+
+impl TokenPath {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Token => "/token/:id",
+            Self::Chart => "/token/:id/chart",
+            Self::Trades => "/token/:id/trades",
+            Self::Threads => "/token/:id/threads",
+            Self::Holders => "/token/:id/holders",
+        }
+    }
+
+    pub fn docs_str(&self) -> &'static str {
+        match self {
+            Self::Token => "/token/{id}",
+            Self::Chart => "/token/{id}/chart",
+            Self::Trades => "/token/{id}/trades",
+            Self::Threads => "/token/{id}/threads",
+            Self::Holders => "/token/{id}/holders",
+        }
+    }
+}
@@ -3,7 +3,8 @@ use std::sync::Arc;
 use crate::{
     db::{postgres::PostgresDatabase, redis::RedisDatabase},
     event::{
-        new_content::NewContentEventProducer, order::OrderEventProducer, token::TokenEventProducer,
+        king::KingEventProducer, new_content::NewContentEventProducer, order::OrderEventProducer,
+        search::TokenSearchIndex, token::TokenEventProducer,
     },
 };
 
@@ -14,4 +15,6 @@ pub struct AppState {
     pub order_event_producer: Arc<OrderEventProducer>,
     pub token_event_producer: Arc<TokenEventProducer>,
     pub new_content_producer: Arc<NewContentEventProducer>,
+    pub king_event_producer: Arc<KingEventProducer>,
+    pub search_index: Arc<TokenSearchIndex>,
 }
@@ -0,0 +1,131 @@
+use crate::{
+    constant::change_channels::{CURVE, TOKEN},
+    types::event::capture::AchievementCapture,
+};
+
+/// Percentage the curve price has to fall below a token's top buy before
+/// the buyer earns `left_curve`.
+pub const LEFT_CURVE_DRAWDOWN: i32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AchievementRule {
+    /// Creator of a token that got listed.
+    ListedCreator,
+    /// Buyer of a token's highest priced buy once the curve price has dropped
+    /// by `drawdown` percent from it.
+    TopBuyer { drawdown: i32 },
+}
+
+pub struct AchievementDefinition {
+    pub name: &'static str,
+    pub rule: AchievementRule,
+}
+
+/// `rugger` is seeded but has no rule yet, so it is never awarded.
+pub const ACHIEVEMENT_RULES: [AchievementDefinition; 2] = [
+    AchievementDefinition {
+        name: "chad_creator",
+        rule: AchievementRule::ListedCreator,
+    },
+    AchievementDefinition {
+        name: "left_curve",
+        rule: AchievementRule::TopBuyer {
+            drawdown: LEFT_CURVE_DRAWDOWN,
+        },
+    },
+];
+
+impl AchievementRule {
+    pub fn channel(&self) -> &'static str {
+        match self {
+            Self::ListedCreator => TOKEN,
+            Self::TopBuyer { .. } => CURVE,
+        }
+    }
+
+    /// Cheap check on the captured row itself, so only candidate events hit the database.
+    pub fn matches(&self, event: &AchievementCapture) -> bool {
+        match (self, event) {
+            (Self::ListedCreator, AchievementCapture::Token(token)) => token.is_listing,
+            (Self::TopBuyer { .. }, AchievementCapture::Curve(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl AchievementCapture {
+    pub fn token_id(&self) -> &str {
+        match self {
+            Self::Token(token) => &token.id,
+            Self::Curve(curve) => &curve.token_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bigdecimal::{BigDecimal, Zero};
+
+    use super::*;
+    use crate::types::model::{Curve, Token};
+
+    fn token(is_listing: bool) -> AchievementCapture {
+        AchievementCapture::Token(Token {
+            id: "0xtoken".into(),
+            name: "Moon Cat".into(),
+            symbol: "MCAT".into(),
+            creator: "0xcreator".into(),
+            description: None,
+            twitter: None,
+            telegram: None,
+            website: None,
+            image_uri: String::new(),
+            is_listing,
+            pair: None,
+            created_at: 0,
+            create_transaction_hash: "0xhash".into(),
+            is_updated: false,
+        })
+    }
+
+    fn curve() -> AchievementCapture {
+        AchievementCapture::Curve(Curve {
+            id: "0xcurve".into(),
+            token_id: "0xtoken".into(),
+            virtual_nad: BigDecimal::zero(),
+            virtual_token: BigDecimal::zero(),
+            reserve_token: BigDecimal::zero(),
+            latest_trade_at: 0,
+            price: BigDecimal::zero(),
+            created_at: 0,
+        })
+    }
+
+    #[test]
+    fn listed_creator_matches_listed_tokens_only() {
+        let rule = AchievementRule::ListedCreator;
+
+        assert_eq!(rule.channel(), TOKEN);
+        assert!(rule.matches(&token(true)));
+        assert!(!rule.matches(&token(false)));
+        assert!(!rule.matches(&curve()));
+    }
+
+    #[test]
+    fn top_buyer_matches_curve_updates_only() {
+        let rule = AchievementRule::TopBuyer {
+            drawdown: LEFT_CURVE_DRAWDOWN,
+        };
+
+        assert_eq!(rule.channel(), CURVE);
+        assert!(rule.matches(&curve()));
+        assert!(!rule.matches(&token(true)));
+    }
+
+    #[test]
+    fn captures_expose_token_id() {
+        for event in [token(true), curve()] {
+            assert_eq!(event.token_id(), "0xtoken");
+        }
+    }
+}
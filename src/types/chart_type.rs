@@ -13,6 +13,33 @@ pub enum ChartType {
     OneDay,
 }
 
+impl ChartType {
+    pub fn table_name(&self) -> &'static str {
+        match self {
+            ChartType::OneMinute => "chart_1m",
+            ChartType::FiveMinutes => "chart_5m",
+            ChartType::FifteenMinutes => "chart_15m",
+            ChartType::ThirtyMinutes => "chart_30m",
+            ChartType::OneHour => "chart_1h",
+            ChartType::FourHours => "chart_4h",
+            ChartType::OneDay => "chart_1d",
+        }
+    }
+
+    /// Candle width in seconds
+    pub fn interval(&self) -> i64 {
+        match self {
+            ChartType::OneMinute => 60,
+            ChartType::FiveMinutes => 300,
+            ChartType::FifteenMinutes => 900,
+            ChartType::ThirtyMinutes => 1800,
+            ChartType::OneHour => 3600,
+            ChartType::FourHours => 14400,
+            ChartType::OneDay => 86400,
+        }
+    }
+}
+
 impl FromStr for ChartType {
     type Err = String;
 
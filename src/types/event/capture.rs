@@ -17,11 +17,17 @@ pub enum NewContentCapture {
     NewToken(Token),
 }
 
+#[derive(Clone, Debug)]
+pub enum AchievementCapture {
+    Token(Token),
+    Curve(Curve),
+}
+
 #[derive(Debug, Clone)]
 pub enum OrderEventCapture {
     CreationTime(Token),
     BumpOrder(Swap),
     ReplyChange(TokenReplyCount),
     MartKetCap(Curve),
-    // ThreadChange(Thread),
+    ThreadChange(ThreadWrapper),
 }
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::order::OrderTokenResponse;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct KingToken {
+    pub token: OrderTokenResponse,
+    #[schema(value_type = String, example = "2023-06-01T12:00:00Z")]
+    pub crowned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KingMessage {
+    pub king: Option<KingToken>,
+}
@@ -1,6 +1,8 @@
 pub mod capture;
+pub mod king;
 pub mod new_content;
 pub mod order;
+pub mod search;
 pub mod token;
 
 use serde::{Deserialize, Serialize};
@@ -1,7 +1,10 @@
 use std::str::FromStr;
 
 use super::{NewSwapMessage, NewTokenMessage, SendMessageType, UserInfo};
-use crate::types::model::{Curve, Swap, Token, TokenReplyCount};
+use crate::types::model::{Curve, Swap, Token, TokenReplyCount, NAD_DECIMALS};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 
 use sqlx::FromRow;
@@ -54,4 +57,51 @@ pub struct OrderTokenResponse {
     pub reply_count: String, //token.id -> token_reply_count table -> select count
     pub price: String,       // token.id -> curve table -> select price
     pub created_at: i64,
+    /// Price times circulating supply, in wei
+    #[serde(default)]
+    pub market_cap: String,
+    /// `market_cap` at the `NAD_USD_PRICE` rate, when configured
+    #[serde(default)]
+    pub market_cap_usd: Option<String>,
+}
+
+lazy_static! {
+    /// USD per NAD, from the optional `NAD_USD_PRICE` environment variable
+    static ref NAD_USD_PRICE: Option<BigDecimal> = std::env::var("NAD_USD_PRICE")
+        .ok()
+        .and_then(|price| BigDecimal::from_str(&price).ok());
+}
+
+/// Converts a market cap in wei to USD, if a NAD price is configured
+pub fn market_cap_usd(market_cap: &str) -> Option<String> {
+    let price = NAD_USD_PRICE.as_ref()?;
+    let market_cap = BigDecimal::from_str(market_cap).ok()?;
+    Some(wei_to_usd(&market_cap, price).to_string())
+}
+
+fn wei_to_usd(wei: &BigDecimal, nad_usd_price: &BigDecimal) -> BigDecimal {
+    let nad = wei / BigDecimal::from(10u64.pow(NAD_DECIMALS));
+    (nad * nad_usd_price).round(2)
+}
+
+/// `latest_reply` order score of a thread, its creation time in epoch milliseconds
+pub fn latest_reply_score(created_at: &DateTime<Utc>) -> String {
+    created_at.timestamp_millis().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wei_market_cap_is_converted_to_usd() {
+        // 8.44 NAD at 2.5 USD per NAD
+        let wei = BigDecimal::from_str("8440000000000000000").unwrap();
+        let price = BigDecimal::from_str("2.5").unwrap();
+
+        assert_eq!(
+            wei_to_usd(&wei, &price),
+            BigDecimal::from_str("21.10").unwrap()
+        );
+    }
 }
@@ -0,0 +1,13 @@
+use serde::Serialize;
+
+use super::order::OrderTokenResponse;
+
+pub const SUGGESTION_LIMIT: usize = 10;
+pub const MAX_SUGGESTION_LIMIT: usize = 20;
+
+/// Suggestions for one `search` request, tagged with the query they answer
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchMessage {
+    pub query: String,
+    pub tokens: Vec<OrderTokenResponse>,
+}
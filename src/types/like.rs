@@ -0,0 +1,25 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::{model::Account, thread::ThreadPost};
+
+/// Accounts that liked an account or a thread, most recent like first
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LikersResponse {
+    pub likers: Vec<Account>,
+    pub next_cursor: Option<String>,
+}
+
+/// Threads liked by an account, most recent like first
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LikedThreadsResponse {
+    pub threads: Vec<ThreadPost>,
+    pub next_cursor: Option<String>,
+}
+
+/// Accounts liked by an account, most recent like first
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LikedAccountsResponse {
+    pub accounts: Vec<Account>,
+    pub next_cursor: Option<String>,
+}
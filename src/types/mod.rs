@@ -1,4 +1,14 @@
+pub mod achievement;
 pub mod chart_type;
 pub mod event;
+pub mod like;
 pub mod model;
+pub mod pagination;
+pub mod portfolio;
 pub mod profile;
+pub mod search;
+pub mod siwe;
+pub mod thread;
+pub mod token;
+pub mod trade;
+pub mod validation;
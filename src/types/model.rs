@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use bigdecimal::{BigDecimal, RoundingMode};
+use bigdecimal::{BigDecimal, RoundingMode, Signed, Zero};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
@@ -54,28 +54,65 @@ pub struct Token {
     pub create_transaction_hash: String,
     pub is_updated: bool,
 }
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct Curve {
     #[serde(rename(serialize = "curve_id"))]
     pub id: String,
     pub token_id: String,
+    #[schema(value_type = String)]
     pub virtual_nad: BigDecimal,
+    #[schema(value_type = String)]
     pub virtual_token: BigDecimal,
+    #[schema(value_type = String)]
     pub reserve_token: BigDecimal,
     pub latest_trade_at: i64,
     #[serde(serialize_with = "serialize_price_bigdecimal")]
+    #[schema(value_type = String)]
     pub price: BigDecimal,
     pub created_at: i64,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, sqlx::FromRow)]
+/// Whole tokens minted per token, all of which start out in the bonding curve
+pub const TOKEN_TOTAL_SUPPLY: i64 = 1_000_000_000;
+/// Decimals of the token contracts; `curve.reserve_token` and balances are
+/// stored in these raw units
+pub const TOKEN_DECIMALS: u32 = 18;
+/// Decimals of NAD; `nad_amount`s and market caps are stored in wei
+pub const NAD_DECIMALS: u32 = 18;
+
+impl Curve {
+    /// Total supply in raw token units
+    pub fn total_supply() -> BigDecimal {
+        BigDecimal::from(TOKEN_TOTAL_SUPPLY) * BigDecimal::from(10u64.pow(TOKEN_DECIMALS))
+    }
+
+    /// Tokens bought out of the curve so far, never negative
+    pub fn circulating_supply(&self) -> BigDecimal {
+        let circulating = Self::total_supply() - &self.reserve_token;
+        if circulating.is_negative() {
+            BigDecimal::zero()
+        } else {
+            circulating
+        }
+    }
+
+    /// Price times circulating supply, in wei. `price` is NAD per whole token,
+    /// so applying it to raw token units gives raw NAD units.
+    pub fn market_cap(&self) -> BigDecimal {
+        (&self.price * self.circulating_supply()).round(0)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, sqlx::FromRow, ToSchema)]
 pub struct Swap {
     #[serde(skip_serializing)]
     pub id: i32,
     pub token_id: String,
     pub sender: String,
     pub is_buy: bool,
+    #[schema(value_type = String)]
     pub nad_amount: BigDecimal,
+    #[schema(value_type = String)]
     pub token_amount: BigDecimal,
     pub created_at: i64,
     pub transaction_hash: String,
@@ -88,14 +125,26 @@ pub struct Chart {
     #[serde(skip_serializing)]
     pub token_id: String,
     #[serde(serialize_with = "serialize_price_bigdecimal")]
+    #[schema(value_type = String)]
     pub open_price: BigDecimal,
     #[serde(serialize_with = "serialize_price_bigdecimal")]
+    #[schema(value_type = String)]
     pub close_price: BigDecimal,
     #[serde(serialize_with = "serialize_price_bigdecimal")]
+    #[schema(value_type = String)]
     pub high_price: BigDecimal,
     #[serde(serialize_with = "serialize_price_bigdecimal")]
+    #[schema(value_type = String)]
     pub low_price: BigDecimal,
     pub time_stamp: i64,
+    #[serde(default)]
+    #[schema(value_type = String)]
+    pub nad_volume: BigDecimal,
+    #[serde(default)]
+    #[schema(value_type = String)]
+    pub token_volume: BigDecimal,
+    #[serde(default)]
+    pub trade_count: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -119,6 +168,14 @@ pub struct Account {
     pub like_count: i32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Achievement {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub name: String,
+    pub image_uri: String,
+}
+
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct AccountSession {
     pub id: String,
@@ -142,6 +199,13 @@ pub struct Thread {
     pub image_uri: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct King {
+    pub id: i32,
+    pub token_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct TokenReplyCount {
     #[serde(skip_serializing)]
@@ -204,6 +268,11 @@ impl ThreadWrapper {
         serde_json::from_value(value).context("Failed to deserialize ThreadWrapper")
     }
 }
+#[derive(Debug, Deserialize)]
+pub struct KingWrapper {
+    record: King,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct TokenReplyCountWrapper {
     record: TokenReplyCount,
@@ -248,3 +317,74 @@ impl_from_value!(Token, TokenWrapper);
 impl_from_value!(Curve, CurveWrapper);
 impl_from_value!(Swap, SwapWrapper);
 impl_from_value!(TokenReplyCount, TokenReplyCountWrapper);
+impl_from_value!(King, KingWrapper);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn curve(virtual_token: &str, reserve_token: &str, price: &str) -> Curve {
+        Curve {
+            id: "0xcurve".into(),
+            token_id: "0xtoken".into(),
+            virtual_nad: BigDecimal::zero(),
+            virtual_token: BigDecimal::from_str(virtual_token).unwrap(),
+            reserve_token: BigDecimal::from_str(reserve_token).unwrap(),
+            latest_trade_at: 0,
+            price: BigDecimal::from_str(price).unwrap(),
+            created_at: 0,
+        }
+    }
+
+    fn decimal(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn market_cap_is_zero_at_creation() {
+        let curve = curve(
+            "1073000000000000000000000000",
+            "1000000000000000000000000000",
+            "0.0000000280",
+        );
+
+        assert_eq!(
+            Curve::total_supply(),
+            decimal("1000000000000000000000000000")
+        );
+        assert_eq!(curve.circulating_supply(), BigDecimal::zero());
+        assert_eq!(curve.market_cap(), BigDecimal::zero());
+    }
+
+    #[test]
+    fn market_cap_is_in_wei() {
+        // 200M of 1B tokens (18 decimals) bought out of the curve
+        let curve = curve(
+            "873000000000000000000000000",
+            "800000000000000000000000000",
+            "0.0000000422",
+        );
+
+        assert_eq!(
+            curve.circulating_supply(),
+            decimal("200000000000000000000000000")
+        );
+        // 8.44 NAD in wei
+        assert_eq!(curve.market_cap(), decimal("8440000000000000000"));
+    }
+
+    #[test]
+    fn circulating_supply_is_clamped_at_zero() {
+        // Reserve above the total supply, e.g. tokens sent to the curve directly
+        let curve = curve(
+            "1073000000000000000000000000",
+            "1000000000000000000000000001",
+            "0.0000000280",
+        );
+
+        assert_eq!(curve.circulating_supply(), BigDecimal::zero());
+        assert_eq!(curve.market_cap(), BigDecimal::zero());
+    }
+}
@@ -0,0 +1,88 @@
+use std::{fmt, str::FromStr};
+
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+pub const PAGE_LIMIT: i64 = 20;
+pub const MAX_PAGE_LIMIT: i64 = 100;
+
+/// Keyset cursor over `(key, id)`, encoded as `{key}_{id}`. Lists sorted by a
+/// NUMERIC column or tie-broken by an address use `BigDecimal` keys or `String` ids.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<K = i64, I = i32> {
+    pub key: K,
+    pub id: I,
+}
+
+impl<K: FromStr, I: FromStr> FromStr for Cursor<K, I> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, id) = s
+            .split_once('_')
+            .ok_or_else(|| format!("Invalid cursor: {}", s))?;
+        Ok(Cursor {
+            key: key.parse().map_err(|_| format!("Invalid cursor: {}", s))?,
+            id: id.parse().map_err(|_| format!("Invalid cursor: {}", s))?,
+        })
+    }
+}
+
+impl<K: fmt::Display, I: fmt::Display> fmt::Display for Cursor<K, I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}_{}", self.key, self.id)
+    }
+}
+
+/// Parses an optional `cursor` query parameter
+pub fn parse_cursor<K: FromStr, I: FromStr>(
+    cursor: Option<String>,
+) -> Result<Option<Cursor<K, I>>, String> {
+    cursor.map(|cursor| Cursor::from_str(&cursor)).transpose()
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PaginationQuery {
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Maximum number of items to return
+    pub limit: Option<i64>,
+}
+
+/// Validated `cursor` and `limit` query parameters, extracted as a whole by handlers.
+/// Lists keyed by their row id alone page with a plain `i32` cursor.
+#[derive(Debug, Clone, Copy)]
+pub struct Pagination<C = Cursor> {
+    pub cursor: Option<C>,
+    pub limit: i64,
+}
+
+impl<C: FromStr> TryFrom<PaginationQuery> for Pagination<C> {
+    type Error = String;
+
+    fn try_from(query: PaginationQuery) -> Result<Self, Self::Error> {
+        Ok(Pagination {
+            cursor: query
+                .cursor
+                .map(|cursor| {
+                    cursor
+                        .parse()
+                        .map_err(|_| format!("Invalid cursor: {}", cursor))
+                })
+                .transpose()?,
+            limit: query.limit.unwrap_or(PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT),
+        })
+    }
+}
+
+impl<C: fmt::Display> Pagination<C> {
+    /// Cursor for the next page, or `None` once a page comes back short
+    pub fn next_cursor(&self, len: usize, last: Option<C>) -> Option<String> {
+        if len as i64 == self.limit {
+            last.map(|cursor| cursor.to_string())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::model::{Swap, Token};
+
+/// Position in one token rebuilt from swaps with the average cost method.
+/// All amounts are raw NAD units, like `swap.nad_amount`.
+#[derive(Debug, Clone, Default)]
+pub struct Position {
+    /// Tokens bought through swaps and not sold yet
+    pub quantity: BigDecimal,
+    /// NAD paid for `quantity`
+    pub cost: BigDecimal,
+    pub realized_pnl: BigDecimal,
+}
+
+impl Position {
+    pub fn apply(&mut self, swap: &Swap) {
+        if swap.is_buy {
+            self.quantity += &swap.token_amount;
+            self.cost += &swap.nad_amount;
+            return;
+        }
+
+        // Tokens sold beyond what was bought came from transfers and carry no cost
+        let sold = if swap.token_amount < self.quantity {
+            swap.token_amount.clone()
+        } else {
+            self.quantity.clone()
+        };
+        let sold_cost = if self.quantity.is_zero() {
+            BigDecimal::zero()
+        } else {
+            &self.cost * &sold / &self.quantity
+        };
+        self.realized_pnl += &swap.nad_amount - &sold_cost;
+        self.cost -= sold_cost;
+        self.quantity -= sold;
+    }
+
+    /// Cost of `balance` at the average price paid, ignoring tokens received by transfer
+    pub fn cost_basis(&self, balance: &BigDecimal) -> BigDecimal {
+        if self.quantity.is_zero() {
+            BigDecimal::zero()
+        } else if balance < &self.quantity {
+            &self.cost * balance / &self.quantity
+        } else {
+            self.cost.clone()
+        }
+    }
+}
+
+/// Rounds a NAD amount to whole raw units
+pub fn round_nad(amount: BigDecimal) -> BigDecimal {
+    amount.with_scale_round(0, RoundingMode::HalfEven)
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PortfolioToken {
+    pub token: Token,
+    #[schema(value_type = String)]
+    pub balance: BigDecimal,
+    #[schema(value_type = String)]
+    pub price: BigDecimal,
+    /// `balance` at the current curve price, in NAD
+    #[schema(value_type = String)]
+    pub value: BigDecimal,
+    #[schema(value_type = String)]
+    pub cost_basis: BigDecimal,
+    #[schema(value_type = String)]
+    pub realized_pnl: BigDecimal,
+    /// `value` minus `cost_basis`
+    #[schema(value_type = String)]
+    pub unrealized_pnl: BigDecimal,
+}
+
+/// Tokens the account holds or has traded, highest value first
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PortfolioResponse {
+    pub tokens: Vec<PortfolioToken>,
+    #[schema(value_type = String)]
+    pub total_value: BigDecimal,
+    #[schema(value_type = String)]
+    pub total_cost_basis: BigDecimal,
+    #[schema(value_type = String)]
+    pub total_realized_pnl: BigDecimal,
+    #[schema(value_type = String)]
+    pub total_unrealized_pnl: BigDecimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swap(is_buy: bool, token_amount: i64, nad_amount: i64) -> Swap {
+        Swap {
+            id: 0,
+            token_id: "0xtoken".into(),
+            sender: "0xtrader".into(),
+            is_buy,
+            nad_amount: BigDecimal::from(nad_amount),
+            token_amount: BigDecimal::from(token_amount),
+            created_at: 0,
+            transaction_hash: "0xhash".into(),
+        }
+    }
+
+    fn position(swaps: &[Swap]) -> Position {
+        let mut position = Position::default();
+        for swap in swaps {
+            position.apply(swap);
+        }
+        position
+    }
+
+    #[test]
+    fn buys_accumulate_quantity_and_cost() {
+        let position = position(&[swap(true, 100, 1_000), swap(true, 100, 3_000)]);
+
+        assert_eq!(position.quantity, BigDecimal::from(200));
+        assert_eq!(position.cost, BigDecimal::from(4_000));
+        assert_eq!(position.realized_pnl, BigDecimal::zero());
+        assert_eq!(
+            position.cost_basis(&BigDecimal::from(200)),
+            BigDecimal::from(4_000)
+        );
+    }
+
+    #[test]
+    fn partial_sell_realizes_against_average_cost() {
+        // Average price 20, so 50 tokens cost 1000 and sell for 1500
+        let position = position(&[
+            swap(true, 100, 1_000),
+            swap(true, 100, 3_000),
+            swap(false, 50, 1_500),
+        ]);
+
+        assert_eq!(position.quantity, BigDecimal::from(150));
+        assert_eq!(position.cost, BigDecimal::from(3_000));
+        assert_eq!(position.realized_pnl, BigDecimal::from(500));
+        assert_eq!(
+            position.cost_basis(&BigDecimal::from(150)),
+            BigDecimal::from(3_000)
+        );
+        // Part of the balance was transferred out
+        assert_eq!(
+            position.cost_basis(&BigDecimal::from(75)),
+            BigDecimal::from(1_500)
+        );
+        // Extra tokens transferred in carry no cost
+        assert_eq!(
+            position.cost_basis(&BigDecimal::from(300)),
+            BigDecimal::from(3_000)
+        );
+    }
+
+    #[test]
+    fn full_sell_at_a_loss_clears_the_position() {
+        let position = position(&[swap(true, 100, 1_000), swap(false, 100, 800)]);
+
+        assert_eq!(position.quantity, BigDecimal::zero());
+        assert_eq!(position.cost, BigDecimal::zero());
+        assert_eq!(position.realized_pnl, BigDecimal::from(-200));
+    }
+
+    #[test]
+    fn oversell_of_transferred_tokens_is_realized_at_zero_cost() {
+        // 50 of the 150 sold tokens were received by transfer
+        let position = position(&[swap(true, 100, 1_000), swap(false, 150, 3_000)]);
+
+        assert_eq!(position.quantity, BigDecimal::zero());
+        assert_eq!(position.cost, BigDecimal::zero());
+        assert_eq!(position.realized_pnl, BigDecimal::from(2_000));
+        assert_eq!(
+            position.cost_basis(&BigDecimal::from(50)),
+            BigDecimal::zero()
+        );
+    }
+
+    #[test]
+    fn sell_without_quantity_realizes_the_whole_amount() {
+        let position = position(&[swap(false, 10, 500)]);
+
+        assert_eq!(position.quantity, BigDecimal::zero());
+        assert_eq!(position.cost, BigDecimal::zero());
+        assert_eq!(position.realized_pnl, BigDecimal::from(500));
+        assert_eq!(
+            position.cost_basis(&BigDecimal::from(10)),
+            BigDecimal::zero()
+        );
+        assert_eq!(
+            Position::default().cost_basis(&BigDecimal::zero()),
+            BigDecimal::zero()
+        );
+    }
+
+    #[test]
+    fn uneven_average_cost_rounds_to_whole_units() {
+        // One of three tokens bought for 10 costs 3.33..
+        let position = position(&[swap(true, 3, 10), swap(false, 1, 5)]);
+
+        assert_eq!(position.quantity, BigDecimal::from(2));
+        assert_eq!(round_nad(position.cost.clone()), BigDecimal::from(7));
+        assert_eq!(
+            round_nad(position.realized_pnl.clone()),
+            BigDecimal::from(2)
+        );
+        assert_eq!(
+            round_nad(position.cost_basis(&BigDecimal::from(1))),
+            BigDecimal::from(3)
+        );
+    }
+}
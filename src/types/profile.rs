@@ -1,17 +1,149 @@
 use bigdecimal::BigDecimal;
-use serde::Serialize;
-use utoipa::ToSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-use super::model::Token;
+use super::{model::Token, thread::ThreadSort, validation::is_http_url};
+
+pub const MIN_NICKNAME_LENGTH: usize = 3;
+pub const MAX_NICKNAME_LENGTH: usize = 20;
+pub const MAX_BIO_LENGTH: usize = 255;
+/// Nicknames that could pass for the site or its staff, compared case-insensitively
+pub const RESERVED_NICKNAMES: [&str; 10] = [
+    "admin",
+    "administrator",
+    "mod",
+    "moderator",
+    "nad",
+    "nadfun",
+    "official",
+    "support",
+    "system",
+    "team",
+];
+
+pub fn is_address(user: &str) -> bool {
+    // 주소 형식 검증 로직 (예: 0x로 시작하고 적절한 길이인지 확인)
+    user.starts_with("0x") && user.len() == 42
+}
 
 pub enum Identifier {
     Nickname(String),
     Address(String), // 이더리움 주소
 }
 
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HoldingSort {
+    /// Balance times the current curve price
+    #[default]
+    Value,
+    Balance,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CreatedTokenSort {
+    #[default]
+    Newest,
+    MarketCap,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FollowSort {
+    /// Most recently followed first
+    #[default]
+    Newest,
+    MostFollowers,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HoldingQuery {
+    /// Order of held tokens
+    pub sort: Option<HoldingSort>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CreatedTokenQuery {
+    /// Order of created tokens
+    pub sort: Option<CreatedTokenSort>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProfileReplyQuery {
+    /// Order of replies
+    pub sort: Option<ThreadSort>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FollowQuery {
+    /// Order of followers or followed accounts
+    pub sort: Option<FollowSort>,
+}
+
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct HoldToken {
     pub token: Token,
     pub balance: String,
     pub price: String,
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateProfileRequest {
+    pub nickname: Option<String>,
+    pub bio: Option<String>,
+    pub image_uri: Option<String>,
+}
+
+pub fn validate_nickname(nickname: &str) -> Result<(), String> {
+    let length = nickname.chars().count();
+    if !(MIN_NICKNAME_LENGTH..=MAX_NICKNAME_LENGTH).contains(&length) {
+        return Err(format!(
+            "Nickname must be {} to {} characters",
+            MIN_NICKNAME_LENGTH, MAX_NICKNAME_LENGTH
+        ));
+    }
+    if !nickname
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err("Nickname may only contain letters, digits and underscores".into());
+    }
+    // Keeps `/profile/:user` routing between nickname and address unambiguous
+    if is_address(nickname) {
+        return Err("Nickname must not look like an address".into());
+    }
+    if RESERVED_NICKNAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(nickname))
+    {
+        return Err(format!("Nickname {} is reserved", nickname));
+    }
+    Ok(())
+}
+
+impl UpdateProfileRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.nickname.is_none() && self.bio.is_none() && self.image_uri.is_none() {
+            return Err("Nothing to update".into());
+        }
+        if let Some(nickname) = &self.nickname {
+            validate_nickname(nickname)?;
+        }
+        if let Some(bio) = &self.bio {
+            if bio.chars().count() > MAX_BIO_LENGTH {
+                return Err(format!("Bio must be at most {} characters", MAX_BIO_LENGTH));
+            }
+        }
+        if let Some(image_uri) = &self.image_uri {
+            if !is_http_url(image_uri) {
+                return Err(format!("Invalid image uri: {}", image_uri));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+pub const SEARCH_LIMIT: i64 = 20;
+pub const MAX_SEARCH_LIMIT: i64 = 50;
+pub const MAX_SEARCH_TERM_LENGTH: usize = 100;
+/// Per section limit of `GET /search`
+pub const UNIFIED_SEARCH_LIMIT: i64 = 5;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSort {
+    /// Text relevance blended with market cap and recency
+    #[default]
+    Relevance,
+    MarketCap,
+    Newest,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// Order of matching tokens
+    pub sort: Option<SearchSort>,
+    /// Maximum number of tokens to return
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchFilter {
+    /// Trimmed, lowercase search term
+    pub term: String,
+    pub sort: SearchSort,
+    pub limit: i64,
+}
+
+impl SearchFilter {
+    pub fn new(term: &str, query: SearchQuery) -> Result<Self, String> {
+        let term = term.trim().to_lowercase();
+        if term.is_empty() {
+            return Err("Search term must not be empty".into());
+        }
+        if term.chars().count() > MAX_SEARCH_TERM_LENGTH {
+            return Err(format!(
+                "Search term must be at most {} characters",
+                MAX_SEARCH_TERM_LENGTH
+            ));
+        }
+
+        Ok(SearchFilter {
+            term,
+            sort: query.sort.unwrap_or_default(),
+            limit: query
+                .limit
+                .unwrap_or(SEARCH_LIMIT)
+                .clamp(1, MAX_SEARCH_LIMIT),
+        })
+    }
+
+    /// `to_tsquery` input matching every word of the term as a prefix, e.g. `moon:* & cat:*`.
+    /// `None` when the term has no searchable words.
+    pub fn prefix_tsquery(&self) -> Option<String> {
+        let words: Vec<String> = self
+            .term
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("{}:*", word))
+            .collect();
+        (!words.is_empty()).then(|| words.join(" & "))
+    }
+
+    /// `LIKE` pattern matching values that start with the term
+    pub fn like_prefix(&self) -> String {
+        let escaped = self
+            .term
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        format!("{}%", escaped)
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UnifiedSearchQuery {
+    /// Token name or symbol, nickname, address or transaction hash
+    pub q: String,
+    /// Maximum number of results per section
+    pub limit: Option<i64>,
+}
+
+pub fn is_transaction_hash(term: &str) -> bool {
+    term.starts_with("0x")
+        && term.len() == 66
+        && term[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionKind {
+    Swap,
+    TokenCreation,
+}
+
+/// Transaction whose hash exactly matches the search term
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TransactionHit {
+    pub kind: TransactionKind,
+    pub transaction_hash: String,
+    pub token_id: String,
+    /// Swap sender or token creator
+    pub account_id: String,
+    pub created_at: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(term: &str) -> SearchFilter {
+        SearchFilter::new(
+            term,
+            SearchQuery {
+                sort: None,
+                limit: None,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn term_is_trimmed_and_lowercased() {
+        assert_eq!(filter("  Moon CAT ").term, "moon cat");
+    }
+
+    #[test]
+    fn empty_and_overlong_terms_are_rejected() {
+        let query = || SearchQuery {
+            sort: None,
+            limit: None,
+        };
+
+        assert!(SearchFilter::new("   ", query()).is_err());
+        assert!(SearchFilter::new(&"a".repeat(MAX_SEARCH_TERM_LENGTH), query()).is_ok());
+        assert!(SearchFilter::new(&"a".repeat(MAX_SEARCH_TERM_LENGTH + 1), query()).is_err());
+    }
+
+    #[test]
+    fn tsquery_prefixes_every_word() {
+        assert_eq!(
+            filter("moon cat").prefix_tsquery().as_deref(),
+            Some("moon:* & cat:*")
+        );
+        assert_eq!(
+            filter("도지 코인").prefix_tsquery().as_deref(),
+            Some("도지:* & 코인:*")
+        );
+    }
+
+    #[test]
+    fn tsquery_drops_operators_and_punctuation() {
+        assert_eq!(
+            filter("moon-cat!").prefix_tsquery().as_deref(),
+            Some("moon:* & cat:*")
+        );
+        assert_eq!(
+            filter("a&b | !c:*'d'").prefix_tsquery().as_deref(),
+            Some("a:* & b:* & c:* & d:*")
+        );
+        assert_eq!(filter("%_\\ & | !").prefix_tsquery(), None);
+    }
+
+    #[test]
+    fn like_prefix_escapes_wildcards() {
+        assert_eq!(filter("moon").like_prefix(), "moon%");
+        assert_eq!(filter("50%").like_prefix(), "50\\%%");
+        assert_eq!(filter("moon_cat").like_prefix(), "moon\\_cat%");
+        assert_eq!(filter("a\\b").like_prefix(), "a\\\\b%");
+        // The backslash is escaped before the escapes it introduces
+        assert_eq!(filter("\\%").like_prefix(), "\\\\\\%%");
+    }
+
+    #[test]
+    fn transaction_hash_needs_32_hex_bytes() {
+        let hash = format!("0x{}", "ab".repeat(32));
+
+        assert!(is_transaction_hash(&hash));
+        assert!(is_transaction_hash(&format!("0x{}", "AB".repeat(32))));
+        assert!(!is_transaction_hash(&hash[..65]));
+        assert!(!is_transaction_hash(&format!("{}a", hash)));
+        assert!(!is_transaction_hash(&format!("0x{}", "g".repeat(64))));
+        assert!(!is_transaction_hash(&hash[2..]));
+        // An address is too short
+        assert!(!is_transaction_hash(&format!("0x{}", "ab".repeat(20))));
+    }
+
+    #[test]
+    fn uppercase_transaction_hash_is_detected_after_lowercasing() {
+        let hash = format!("0X{}", "AB".repeat(32));
+
+        assert!(!is_transaction_hash(&hash));
+        assert!(is_transaction_hash(&filter(&hash).term));
+    }
+}
@@ -0,0 +1,432 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use utoipa::ToSchema;
+
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+/// EIP-4361 (Sign-In with Ethereum) message
+#[derive(Debug, Clone)]
+pub struct SiweMessage {
+    pub domain: String,
+    /// Lowercase `0x…` address
+    pub address: String,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub not_before: Option<DateTime<Utc>>,
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(value)
+        .with_context(|| format!("Invalid timestamp: {}", value))?
+        .with_timezone(&Utc))
+}
+
+impl FromStr for SiweMessage {
+    type Err = anyhow::Error;
+
+    fn from_str(message: &str) -> Result<Self> {
+        let mut lines = message.lines();
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(PREAMBLE))
+            .context("Missing sign-in preamble")?
+            .to_string();
+        let address = lines.next().context("Missing address")?.to_lowercase();
+        if !(address.len() == 42
+            && address.starts_with("0x")
+            && address[2..].chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            bail!("Invalid address: {}", address);
+        }
+
+        let mut statement = None;
+        let mut uri = None;
+        let mut version = None;
+        let mut chain_id = None;
+        let mut nonce = None;
+        let mut issued_at = None;
+        let mut expiration_time = None;
+        let mut not_before = None;
+
+        for line in lines {
+            match line.split_once(": ") {
+                Some(("URI", value)) => uri = Some(value.to_string()),
+                Some(("Version", value)) => version = Some(value.to_string()),
+                Some(("Chain ID", value)) => {
+                    chain_id = Some(value.parse().context("Invalid chain id")?)
+                }
+                Some(("Nonce", value)) => nonce = Some(value.to_string()),
+                Some(("Issued At", value)) => issued_at = Some(parse_time(value)?),
+                Some(("Expiration Time", value)) => expiration_time = Some(parse_time(value)?),
+                Some(("Not Before", value)) => not_before = Some(parse_time(value)?),
+                // Statement sits between the address and the fields
+                _ if uri.is_none() && !line.is_empty() => statement = Some(line.to_string()),
+                _ => {}
+            }
+        }
+
+        Ok(SiweMessage {
+            domain,
+            address,
+            statement,
+            uri: uri.context("Missing URI")?,
+            version: version.context("Missing version")?,
+            chain_id: chain_id.context("Missing chain id")?,
+            nonce: nonce.context("Missing nonce")?,
+            issued_at: issued_at.context("Missing issued at")?,
+            expiration_time,
+            not_before,
+        })
+    }
+}
+
+impl SiweMessage {
+    /// Checks everything but the nonce and the signature
+    pub fn validate(&self, domain: &str, now: DateTime<Utc>) -> Result<()> {
+        if self.domain != domain {
+            bail!("Unexpected domain: {}", self.domain);
+        }
+        if self.version != "1" {
+            bail!("Unsupported version: {}", self.version);
+        }
+        if self
+            .expiration_time
+            .is_some_and(|expiration| expiration <= now)
+        {
+            bail!("Message expired");
+        }
+        if self.not_before.is_some_and(|not_before| not_before > now) {
+            bail!("Message not yet valid");
+        }
+        Ok(())
+    }
+
+    /// Parses a signed message and checks its domain, validity window and signer.
+    /// The nonce is left to `sign_in`.
+    pub fn verify(
+        message: &str,
+        signature: &str,
+        domain: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Self, SignInError> {
+        let parsed = SiweMessage::from_str(message).map_err(SignInError::Malformed)?;
+        parsed
+            .validate(domain, now)
+            .map_err(SignInError::Rejected)?;
+
+        let signer = recover_address(message, signature).map_err(SignInError::Rejected)?;
+        if signer != parsed.address {
+            return Err(SignInError::Rejected(anyhow!(
+                "Signature does not match address"
+            )));
+        }
+        Ok(parsed)
+    }
+}
+
+#[derive(Debug)]
+pub enum SignInError {
+    /// Not a well-formed EIP-4361 message
+    Malformed(anyhow::Error),
+    /// Wrong domain, validity window, signer or nonce
+    Rejected(anyhow::Error),
+    /// The nonce store could not be reached
+    Store(anyhow::Error),
+}
+
+/// Issued sign-in nonces, each usable once
+#[async_trait]
+pub trait NonceStore {
+    /// Consumes the nonce, returning whether it was issued and not yet expired or used
+    async fn take_nonce(&self, nonce: &str) -> Result<bool>;
+}
+
+/// Verifies a signed sign-in request and consumes its nonce
+pub async fn sign_in(
+    store: &(impl NonceStore + Sync),
+    request: &VerifyRequest,
+    domain: &str,
+    now: DateTime<Utc>,
+) -> Result<SiweMessage, SignInError> {
+    let message = SiweMessage::verify(&request.message, &request.signature, domain, now)?;
+    let issued = store
+        .take_nonce(&message.nonce)
+        .await
+        .map_err(SignInError::Store)?;
+    if !issued {
+        return Err(SignInError::Rejected(anyhow!("Invalid or expired nonce")));
+    }
+    Ok(message)
+}
+
+/// Recovers the lowercase `0x…` address that produced an EIP-191 `personal_sign` signature
+pub fn recover_address(message: &str, signature: &str) -> Result<String> {
+    let bytes = hex::decode(signature.trim_start_matches("0x")).context("Invalid signature hex")?;
+    if bytes.len() != 65 {
+        bail!("Invalid signature length: {}", bytes.len());
+    }
+    let signature = Signature::from_slice(&bytes[..64]).context("Invalid signature")?;
+    let v = match bytes[64] {
+        27 | 28 => bytes[64] - 27,
+        v => v,
+    };
+    let recovery_id = RecoveryId::from_byte(v).ok_or_else(|| anyhow!("Invalid recovery id"))?;
+
+    let prefixed = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+    let hash = Keccak256::digest(prefixed.as_bytes());
+    let key = VerifyingKey::recover_from_prehash(&hash, &signature, recovery_id)
+        .context("Failed to recover signer")?;
+
+    let public_key = key.to_encoded_point(false);
+    let address_hash = Keccak256::digest(&public_key.as_bytes()[1..]);
+    Ok(format!("0x{}", hex::encode(&address_hash[12..])))
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NonceResponse {
+    pub nonce: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerifyRequest {
+    /// EIP-4361 message that was signed
+    pub message: String,
+    /// `personal_sign` signature over `message`
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerifyResponse {
+    pub account_id: String,
+    /// Also set as the `session_id` cookie; send as `Authorization: Bearer` otherwise
+    pub session_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::Mutex};
+
+    use chrono::TimeZone;
+    use k256::ecdsa::SigningKey;
+
+    use super::*;
+
+    const DOMAIN: &str = "nad.fun";
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_slice(&[seed; 32]).unwrap()
+    }
+
+    fn address_of(key: &SigningKey) -> String {
+        let public_key = key.verifying_key().to_encoded_point(false);
+        let hash = Keccak256::digest(&public_key.as_bytes()[1..]);
+        format!("0x{}", hex::encode(&hash[12..]))
+    }
+
+    fn personal_sign(key: &SigningKey, message: &str) -> String {
+        let prefixed = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+        let hash = Keccak256::digest(prefixed.as_bytes());
+        let (signature, recovery_id) = key.sign_prehash_recoverable(&hash).unwrap();
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(recovery_id.to_byte() + 27);
+        format!("0x{}", hex::encode(bytes))
+    }
+
+    fn time(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 1, hour, 0, 0).unwrap()
+    }
+
+    fn message(domain: &str, address: &str, nonce: &str) -> String {
+        format!(
+            "{domain} wants you to sign in with your Ethereum account:\n\
+             {address}\n\
+             \n\
+             Sign in to nad.fun\n\
+             \n\
+             URI: https://{domain}\n\
+             Version: 1\n\
+             Chain ID: 10143\n\
+             Nonce: {nonce}\n\
+             Issued At: 2024-06-01T10:00:00Z\n\
+             Expiration Time: 2024-06-01T12:00:00Z\n\
+             Not Before: 2024-06-01T10:00:00Z"
+        )
+    }
+
+    fn signed_request(key: &SigningKey, message: String) -> VerifyRequest {
+        VerifyRequest {
+            signature: personal_sign(key, &message),
+            message,
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryNonceStore {
+        nonces: Mutex<HashSet<String>>,
+    }
+
+    impl MemoryNonceStore {
+        fn issued(nonce: &str) -> Self {
+            let store = MemoryNonceStore::default();
+            store.nonces.lock().unwrap().insert(nonce.to_string());
+            store
+        }
+    }
+
+    #[async_trait]
+    impl NonceStore for MemoryNonceStore {
+        async fn take_nonce(&self, nonce: &str) -> Result<bool> {
+            Ok(self.nonces.lock().unwrap().remove(nonce))
+        }
+    }
+
+    #[test]
+    fn recovers_known_signature() {
+        // web3.js `accounts.sign("Some data", ...)` example
+        let signature = "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+        assert_eq!(
+            recover_address("Some data", signature).unwrap(),
+            "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23"
+        );
+    }
+
+    #[test]
+    fn parses_message_fields() {
+        let key = signing_key(1);
+        let parsed = SiweMessage::from_str(&message(DOMAIN, &address_of(&key), "abc123")).unwrap();
+
+        assert_eq!(parsed.domain, DOMAIN);
+        assert_eq!(parsed.address, address_of(&key));
+        assert_eq!(parsed.statement.as_deref(), Some("Sign in to nad.fun"));
+        assert_eq!(parsed.uri, "https://nad.fun");
+        assert_eq!(parsed.chain_id, 10143);
+        assert_eq!(parsed.nonce, "abc123");
+        assert_eq!(parsed.issued_at, time(10));
+        assert_eq!(parsed.expiration_time, Some(time(12)));
+        assert_eq!(parsed.not_before, Some(time(10)));
+    }
+
+    #[test]
+    fn verifies_signed_message() {
+        let key = signing_key(1);
+        let request = signed_request(&key, message(DOMAIN, &address_of(&key), "abc123"));
+
+        let verified =
+            SiweMessage::verify(&request.message, &request.signature, DOMAIN, time(11)).unwrap();
+        assert_eq!(verified.address, address_of(&key));
+    }
+
+    #[test]
+    fn accepts_checksummed_address() {
+        let key = signing_key(1);
+        let address = address_of(&key);
+        let checksummed = format!("0x{}", address[2..].to_uppercase());
+        let request = signed_request(&key, message(DOMAIN, &checksummed, "abc123"));
+
+        let verified =
+            SiweMessage::verify(&request.message, &request.signature, DOMAIN, time(11)).unwrap();
+        assert_eq!(verified.address, address);
+    }
+
+    #[test]
+    fn rejects_wrong_domain() {
+        let key = signing_key(1);
+        let request = signed_request(&key, message("evil.example", &address_of(&key), "abc123"));
+
+        let result = SiweMessage::verify(&request.message, &request.signature, DOMAIN, time(11));
+        assert!(matches!(result, Err(SignInError::Rejected(_))));
+    }
+
+    #[test]
+    fn rejects_expired_message() {
+        let key = signing_key(1);
+        let request = signed_request(&key, message(DOMAIN, &address_of(&key), "abc123"));
+
+        let result = SiweMessage::verify(&request.message, &request.signature, DOMAIN, time(12));
+        assert!(matches!(result, Err(SignInError::Rejected(_))));
+    }
+
+    #[test]
+    fn rejects_message_not_yet_valid() {
+        let key = signing_key(1);
+        let request = signed_request(&key, message(DOMAIN, &address_of(&key), "abc123"));
+
+        let result = SiweMessage::verify(&request.message, &request.signature, DOMAIN, time(9));
+        assert!(matches!(result, Err(SignInError::Rejected(_))));
+    }
+
+    #[test]
+    fn rejects_malformed_address() {
+        let key = signing_key(1);
+        for address in [
+            "0x1234",
+            "1111111111111111111111111111111111111111aa",
+            "0xzz11111111111111111111111111111111111111",
+        ] {
+            let request = signed_request(&key, message(DOMAIN, address, "abc123"));
+
+            let result =
+                SiweMessage::verify(&request.message, &request.signature, DOMAIN, time(11));
+            assert!(
+                matches!(result, Err(SignInError::Malformed(_))),
+                "{}",
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_signature_from_other_address() {
+        let key = signing_key(1);
+        let other = signing_key(2);
+        let request = signed_request(&other, message(DOMAIN, &address_of(&key), "abc123"));
+
+        let result = SiweMessage::verify(&request.message, &request.signature, DOMAIN, time(11));
+        assert!(matches!(result, Err(SignInError::Rejected(_))));
+    }
+
+    #[tokio::test]
+    async fn consumes_nonce_once() {
+        let key = signing_key(1);
+        let request = signed_request(&key, message(DOMAIN, &address_of(&key), "abc123"));
+        let store = MemoryNonceStore::issued("abc123");
+
+        assert!(sign_in(&store, &request, DOMAIN, time(11)).await.is_ok());
+        let reused = sign_in(&store, &request, DOMAIN, time(11)).await;
+        assert!(matches!(reused, Err(SignInError::Rejected(_))));
+    }
+
+    #[tokio::test]
+    async fn rejects_nonce_that_was_not_issued() {
+        let key = signing_key(1);
+        let request = signed_request(&key, message(DOMAIN, &address_of(&key), "other"));
+        let store = MemoryNonceStore::issued("abc123");
+
+        let result = sign_in(&store, &request, DOMAIN, time(11)).await;
+        assert!(matches!(result, Err(SignInError::Rejected(_))));
+        // The issued nonce is still usable
+        assert!(store.take_nonce("abc123").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn keeps_nonce_when_signature_is_invalid() {
+        let key = signing_key(1);
+        let other = signing_key(2);
+        let request = signed_request(&other, message(DOMAIN, &address_of(&key), "abc123"));
+        let store = MemoryNonceStore::issued("abc123");
+
+        assert!(sign_in(&store, &request, DOMAIN, time(11)).await.is_err());
+        assert!(store.take_nonce("abc123").await.unwrap());
+    }
+}
@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::{
+    event::UserInfo,
+    model::Thread,
+    pagination::{parse_cursor, Cursor},
+    validation::is_http_url,
+};
+
+pub const THREAD_LIMIT: i64 = 20;
+pub const MAX_THREAD_LIMIT: i64 = 100;
+pub const REPLY_PREVIEW_LIMIT: i64 = 3;
+pub const REPLY_LIMIT: i64 = 20;
+pub const MAX_REPLY_LIMIT: i64 = 100;
+pub const MAX_CONTENT_LENGTH: usize = 1000;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreadSort {
+    #[default]
+    Newest,
+    MostLiked,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ThreadQuery {
+    /// Order of root threads
+    pub sort: Option<ThreadSort>,
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Maximum number of root threads to return
+    pub limit: Option<i64>,
+    /// Number of replies to include under each root thread
+    pub reply_limit: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ThreadFilter {
+    pub sort: ThreadSort,
+    pub cursor: Option<Cursor>,
+    pub limit: i64,
+    pub reply_limit: i64,
+}
+
+impl TryFrom<ThreadQuery> for ThreadFilter {
+    type Error = String;
+
+    fn try_from(query: ThreadQuery) -> Result<Self, Self::Error> {
+        Ok(ThreadFilter {
+            sort: query.sort.unwrap_or_default(),
+            cursor: parse_cursor(query.cursor)?,
+            limit: query
+                .limit
+                .unwrap_or(THREAD_LIMIT)
+                .clamp(1, MAX_THREAD_LIMIT),
+            reply_limit: query
+                .reply_limit
+                .unwrap_or(REPLY_PREVIEW_LIMIT)
+                .clamp(0, MAX_REPLY_LIMIT),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReplyQuery {
+    /// `next_reply_cursor` from the root thread or `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Maximum number of replies to return
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ThreadPost {
+    pub thread: Thread,
+    pub author: UserInfo,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RootThread {
+    pub thread: Thread,
+    pub author: UserInfo,
+    pub replies: Vec<ThreadPost>,
+    pub next_reply_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ThreadsResponse {
+    pub threads: Vec<RootThread>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ThreadRepliesResponse {
+    pub replies: Vec<ThreadPost>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateThreadRequest {
+    pub content: String,
+    /// Root thread to reply to; omit to start a new root thread
+    pub root_id: Option<i32>,
+    pub image_uri: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateThreadRequest {
+    pub content: Option<String>,
+    pub image_uri: Option<String>,
+}
+
+pub fn validate_content(content: &str) -> Result<(), String> {
+    if content.trim().is_empty() {
+        return Err("Content must not be empty".into());
+    }
+    if content.chars().count() > MAX_CONTENT_LENGTH {
+        return Err(format!(
+            "Content must be at most {} characters",
+            MAX_CONTENT_LENGTH
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_image_uri(image_uri: Option<&str>) -> Result<(), String> {
+    match image_uri {
+        Some(image_uri) if !is_http_url(image_uri) => {
+            Err(format!("Invalid image uri: {}", image_uri))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl CreateThreadRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        validate_content(&self.content)?;
+        validate_image_uri(self.image_uri.as_deref())
+    }
+}
+
+impl UpdateThreadRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.content.is_none() && self.image_uri.is_none() {
+            return Err("Nothing to update".into());
+        }
+        if let Some(content) = &self.content {
+            validate_content(content)?;
+        }
+        validate_image_uri(self.image_uri.as_deref())
+    }
+}
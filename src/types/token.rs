@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::{
+    event::UserInfo,
+    model::{Curve, Token},
+    validation::{is_http_url, is_url_on},
+};
+
+pub const MAX_DESCRIPTION_LENGTH: usize = 1000;
+const TWITTER_HOSTS: [&str; 2] = ["twitter.com", "x.com"];
+const TELEGRAM_HOSTS: [&str; 2] = ["t.me", "telegram.me"];
+
+/// Creator edit of a token's metadata. Omitted fields are kept, empty strings clear them.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTokenRequest {
+    pub description: Option<String>,
+    pub twitter: Option<String>,
+    pub telegram: Option<String>,
+    pub website: Option<String>,
+}
+
+impl UpdateTokenRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.description.is_none()
+            && self.twitter.is_none()
+            && self.telegram.is_none()
+            && self.website.is_none()
+        {
+            return Err("Nothing to update".into());
+        }
+        if let Some(description) = &self.description {
+            if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+                return Err(format!(
+                    "Description must be at most {} characters",
+                    MAX_DESCRIPTION_LENGTH
+                ));
+            }
+        }
+        let links = [
+            ("twitter", &self.twitter, Some(&TWITTER_HOSTS)),
+            ("telegram", &self.telegram, Some(&TELEGRAM_HOSTS)),
+            ("website", &self.website, None),
+        ];
+        for (field, url, hosts) in links {
+            let Some(url) = url.as_deref().filter(|url| !url.is_empty()) else {
+                continue;
+            };
+            let valid = match hosts {
+                Some(hosts) => is_url_on(url, hosts),
+                None => is_http_url(url),
+            };
+            if !valid {
+                return Err(format!("Invalid {} url: {}", field, url));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TokenDetail {
+    pub token: Token,
+    pub curve: Option<Curve>,
+    /// `None` when the creator has no account row yet
+    pub creator: Option<UserInfo>,
+    pub reply_count: i32,
+    pub holder_count: i64,
+}
+
+pub const HOLDER_LIMIT: i64 = 20;
+pub const MAX_HOLDER_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HolderQuery {
+    /// Maximum number of holders to return
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TokenHolder {
+    pub account_id: String,
+    /// `None` for balances without an account row, e.g. the bonding curve
+    pub user_info: Option<UserInfo>,
+    pub amount: String,
+    /// Share of the total token balance, in percent
+    pub percentage: String,
+    pub is_creator: bool,
+    /// Whether this balance is the bonding curve reserve
+    pub is_curve: bool,
+}
+
+/// Holder statistics. `holder_count` and `top10_percentage` leave out the
+/// bonding curve reserve, while `total_supply` sums every balance including it.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HolderStats {
+    pub holder_count: i64,
+    pub total_supply: String,
+    /// Share of `total_supply` held by the ten largest holders, in percent
+    pub top10_percentage: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HoldersResponse {
+    pub holders: Vec<TokenHolder>,
+    pub stats: HolderStats,
+}
@@ -0,0 +1,76 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::{
+    event::UserInfo,
+    model::Swap,
+    pagination::{parse_cursor, Cursor},
+};
+
+pub const TRADE_LIMIT: i64 = 50;
+pub const MAX_TRADE_LIMIT: i64 = 200;
+
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TradeQuery {
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Maximum number of trades to return
+    pub limit: Option<i64>,
+    /// Only return buys or sells
+    pub side: Option<TradeSide>,
+    /// Only return trades with at least this NAD amount
+    pub min_nad_amount: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TradeFilter {
+    pub cursor: Option<Cursor>,
+    pub limit: i64,
+    pub is_buy: Option<bool>,
+    pub min_nad_amount: Option<BigDecimal>,
+}
+
+impl TryFrom<TradeQuery> for TradeFilter {
+    type Error = String;
+
+    fn try_from(query: TradeQuery) -> Result<Self, Self::Error> {
+        let cursor = parse_cursor(query.cursor)?;
+        let min_nad_amount = query
+            .min_nad_amount
+            .map(|amount| {
+                BigDecimal::from_str(&amount)
+                    .map_err(|_| format!("Invalid min_nad_amount: {}", amount))
+            })
+            .transpose()?;
+
+        Ok(TradeFilter {
+            cursor,
+            limit: query.limit.unwrap_or(TRADE_LIMIT).clamp(1, MAX_TRADE_LIMIT),
+            is_buy: query.side.map(|side| matches!(side, TradeSide::Buy)),
+            min_nad_amount,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Trade {
+    pub swap: Swap,
+    pub user_info: UserInfo,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TradesResponse {
+    pub trades: Vec<Trade>,
+    pub next_cursor: Option<String>,
+}
@@ -0,0 +1,26 @@
+pub const MAX_URL_LENGTH: usize = 2048;
+
+fn url_host(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    rest.split(['/', '?', '#'])
+        .next()
+        .filter(|host| !host.is_empty())
+}
+
+/// Accepts absolute `http(s)://` URLs with a host and no whitespace
+pub fn is_http_url(url: &str) -> bool {
+    url.len() <= MAX_URL_LENGTH && url_host(url).is_some() && !url.contains(char::is_whitespace)
+}
+
+/// Like [`is_http_url`], additionally requiring the host to be one of `hosts`
+/// or a `www.` subdomain of one
+pub fn is_url_on(url: &str, hosts: &[&str]) -> bool {
+    is_http_url(url)
+        && url_host(url).is_some_and(|host| {
+            let host = host.to_ascii_lowercase();
+            let host = host.strip_prefix("www.").unwrap_or(&host);
+            hosts.contains(&host)
+        })
+}